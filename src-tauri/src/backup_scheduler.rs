@@ -0,0 +1,264 @@
+use crate::game_scanner::SteamLibraries;
+use crate::save_manager::{self, BackupSettings, Game};
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+// Auto-backup. While `auto_backup` is enabled, every game whose saves are present is backed up
+// once its interval has passed since `games.last_backup_time`. The interval is the game's own
+// `backup_interval` when set, otherwise the one from the backup settings.
+
+// How often the scheduler looks for due games
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+// Games whose last auto-backup failed are not tried again before this time (Unix ms)
+static RETRY_AFTER: Lazy<Mutex<HashMap<String, i64>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Serialize)]
+struct AutoBackupEvent {
+    game_id: String,
+    title: String,
+    // "interval", "watcher" or "exit"
+    trigger: &'static str,
+    success: bool,
+    // False when the saves were unchanged and no new backup was needed
+    created: bool,
+    save_id: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduledBackup {
+    pub game_id: String,
+    pub title: String,
+    pub interval: String,
+    // Unix timestamp in milliseconds; None when auto-backup is off for the game
+    pub next_backup_time: Option<i64>,
+}
+
+// Parse an interval such as "15min", "1hour", "2h" or "daily". "off" disables auto-backup.
+pub fn parse_interval(value: &str) -> Result<Option<Duration>, String> {
+    let value = value.trim().to_lowercase();
+    match value.as_str() {
+        "off" | "never" | "manual" => return Ok(None),
+        "hourly" => return Ok(Some(Duration::from_secs(60 * 60))),
+        "daily" => return Ok(Some(Duration::from_secs(24 * 60 * 60))),
+        "weekly" => return Ok(Some(Duration::from_secs(7 * 24 * 60 * 60))),
+        _ => {}
+    }
+
+    let digits = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(digits);
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("Invalid backup interval: {}", value))?;
+    let unit_secs = match unit.trim() {
+        "m" | "min" | "mins" | "minute" | "minutes" => 60,
+        "h" | "hour" | "hours" => 60 * 60,
+        "d" | "day" | "days" => 24 * 60 * 60,
+        _ => return Err(format!("Invalid backup interval: {}", value)),
+    };
+    if amount == 0 {
+        return Err(format!("Backup interval must be positive: {}", value));
+    }
+    let secs = amount
+        .checked_mul(unit_secs)
+        .ok_or_else(|| format!("Backup interval is too long: {}", value))?;
+    Ok(Some(Duration::from_secs(secs)))
+}
+
+// Add an interval to a Unix timestamp in ms, saturating for very long intervals
+fn after_interval(time: i64, interval: Duration) -> i64 {
+    time.saturating_add(i64::try_from(interval.as_millis()).unwrap_or(i64::MAX))
+}
+
+fn game_interval<'a>(game: &'a Game, settings: &'a BackupSettings) -> &'a str {
+    game.backup_interval
+        .as_deref()
+        .unwrap_or(&settings.backup_interval)
+}
+
+// Next time a game is due, given its last backup and when a failed backup may be retried.
+// Games never backed up are due right away.
+fn next_backup_time(
+    last_backup_time: Option<i64>,
+    interval: Duration,
+    retry_after: Option<i64>,
+) -> i64 {
+    let due = last_backup_time
+        .map(|last| after_interval(last, interval))
+        .unwrap_or(0);
+    due.max(retry_after.unwrap_or(0))
+}
+
+fn retry_after(game_id: &str) -> Option<i64> {
+    RETRY_AFTER
+        .lock()
+        .ok()
+        .and_then(|retries| retries.get(game_id).copied())
+}
+
+// Start checking for due backups in the background
+pub fn start(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        println!("Auto-backup scheduler started");
+        loop {
+            tokio::time::sleep(CHECK_INTERVAL).await;
+            if let Err(e) = run_due_backups(&app).await {
+                println!("Auto-backup check failed: {}", e);
+            }
+        }
+    });
+}
+
+async fn run_due_backups(app: &AppHandle) -> Result<(), String> {
+    let settings = save_manager::load_backup_settings()
+        .await
+        .map_err(|e| e.message)?;
+    if !settings.auto_backup {
+        return Ok(());
+    }
+
+    let games = save_manager::get_all_games().await.map_err(|e| e.message)?;
+    let now = Utc::now().timestamp_millis();
+    // Steam libraries are only read once a game is due
+    let mut steam_libraries = None;
+    for game in games {
+        let Ok(Some(interval)) = parse_interval(game_interval(&game, &settings)) else {
+            continue;
+        };
+        let due = next_backup_time(game.last_backup_time, interval, retry_after(&game.id));
+        if due > now {
+            continue;
+        }
+        let steam_libraries = steam_libraries.get_or_insert_with(SteamLibraries::load);
+        if !save_manager::game_saves_present(&game, steam_libraries) {
+            continue;
+        }
+
+        println!("Auto-backup of {} ({}) is due", game.title, game.id);
+        let success = auto_backup(app, game.id.clone(), game.title, "interval").await;
+        if let Ok(mut retries) = RETRY_AFTER.lock() {
+            if success {
+                retries.remove(&game.id);
+            } else {
+                retries.insert(game.id, after_interval(now, interval));
+            }
+        }
+    }
+    Ok(())
+}
+
+// Back up a game without user interaction and report the result in an "auto-backup" event
+pub(crate) async fn auto_backup(
+    app: &AppHandle,
+    game_id: String,
+    title: String,
+    trigger: &'static str,
+) -> bool {
+    // Another trigger is already backing the game up, which covers this one
+    let Some(_in_progress) = save_manager::BackupInProgress::start(&game_id) else {
+        println!(
+            "Backup of {} already in progress, skipping {} backup",
+            game_id, trigger
+        );
+        return true;
+    };
+    let result =
+        save_manager::backup_game_saves(game_id.clone(), None, None, None, None, None).await;
+    let event = match result {
+        Ok(response) => AutoBackupEvent {
+            game_id,
+            title,
+            trigger,
+            success: true,
+            created: response.created,
+            save_id: Some(response.save_file.id),
+            error: None,
+        },
+        Err(e) => {
+            println!("Auto-backup of {} failed: {}", game_id, e.message);
+            AutoBackupEvent {
+                game_id,
+                title,
+                trigger,
+                success: false,
+                created: false,
+                save_id: None,
+                error: Some(e.message),
+            }
+        }
+    };
+    let success = event.success;
+    let _ = app.emit("auto-backup", event);
+    success
+}
+
+// When each game is backed up next
+#[tauri::command]
+pub async fn get_backup_schedule() -> Result<Vec<ScheduledBackup>, String> {
+    let settings = save_manager::load_backup_settings()
+        .await
+        .map_err(|e| e.message)?;
+    let games = save_manager::get_all_games().await.map_err(|e| e.message)?;
+
+    Ok(games
+        .into_iter()
+        .map(|game| {
+            let interval = game_interval(&game, &settings).to_string();
+            let next_backup_time = match parse_interval(&interval) {
+                Ok(Some(duration)) if settings.auto_backup => Some(next_backup_time(
+                    game.last_backup_time,
+                    duration,
+                    retry_after(&game.id),
+                )),
+                _ => None,
+            };
+            ScheduledBackup {
+                game_id: game.id,
+                title: game.title,
+                interval,
+                next_backup_time,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_interval() {
+        let minutes = |m: u64| Ok(Some(Duration::from_secs(m * 60)));
+        assert_eq!(parse_interval("15min"), minutes(15));
+        assert_eq!(parse_interval("30min"), minutes(30));
+        assert_eq!(parse_interval("1hour"), minutes(60));
+        assert_eq!(parse_interval(" 2 Hours "), minutes(120));
+        assert_eq!(parse_interval("daily"), minutes(24 * 60));
+        assert_eq!(parse_interval("off"), Ok(None));
+        assert!(parse_interval("0min").is_err());
+        assert!(parse_interval("soon").is_err());
+        assert!(parse_interval("10").is_err());
+        assert!(parse_interval("99999999999999999d").is_err());
+    }
+
+    #[test]
+    fn test_next_backup_time() {
+        let interval = Duration::from_secs(30 * 60);
+        assert_eq!(next_backup_time(None, interval, None), 0);
+        assert_eq!(next_backup_time(Some(1_000), interval, None), 1_801_000);
+        assert_eq!(
+            next_backup_time(Some(1_000), interval, Some(5_000_000)),
+            5_000_000
+        );
+        let forever = Duration::from_secs(u64::MAX);
+        assert_eq!(next_backup_time(Some(1_000), forever, None), i64::MAX);
+    }
+}
@@ -0,0 +1,674 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+
+// Content-addressed backup store. File contents are stored once under
+// `<saves>/.objects/<first two hex digits>/<rest of the SHA-256>` and every backup is a
+// manifest (`<saves>/<game_id>/<backup name>.manifest.json`) listing the files it holds.
+// Unchanged files are shared between backups; objects no manifest references are removed
+// by garbage collection. Objects may be compressed, marked by a `.zip` or `.zst` extension;
+// the hash is always that of the uncompressed contents.
+
+const OBJECTS_DIR: &str = ".objects";
+// Name of the single entry in a deflate compressed object's zip archive
+const ZIP_ENTRY: &str = "object";
+pub(crate) const MANIFEST_EXTENSION: &str = ".manifest.json";
+
+// Held while objects are written or collected, so a backup in progress never loses objects
+// its manifest doesn't reference yet
+static STORE_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BackupManifest {
+    pub game_id: String,
+    pub created_at: String,
+    pub files: Vec<ManifestFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ManifestFile {
+    // Path relative to the save location, `/` separated
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+    // Modification time of the source file, in Unix seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mtime: Option<i64>,
+    // Compression of the stored object; uncompressed when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<Encoding>,
+}
+
+// How an object's contents are compressed on disk
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Encoding {
+    // Deflate, stored as a single entry zip archive
+    #[serde(alias = "zip")]
+    Deflate,
+    Zstd,
+}
+
+impl Encoding {
+    fn extension(self) -> &'static str {
+        match self {
+            Encoding::Deflate => ".zip",
+            Encoding::Zstd => ".zst",
+        }
+    }
+}
+
+// Compression applied to newly stored objects, with its level
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Compression {
+    None,
+    Deflate(u32),
+    Zstd(i32),
+}
+
+impl Compression {
+    fn encoding(self) -> Option<Encoding> {
+        match self {
+            Compression::None => None,
+            Compression::Deflate(_) => Some(Encoding::Deflate),
+            Compression::Zstd(_) => Some(Encoding::Zstd),
+        }
+    }
+}
+
+// Writes an object through its compressor
+enum ObjectWriter {
+    Plain(File),
+    Deflate(zip::ZipWriter<File>),
+    Zstd(zstd::Encoder<'static, File>),
+}
+
+impl ObjectWriter {
+    fn new(file: File, compression: Compression) -> io::Result<Self> {
+        Ok(match compression {
+            Compression::None => ObjectWriter::Plain(file),
+            Compression::Deflate(level) => {
+                let mut archive = zip::ZipWriter::new(file);
+                let options = zip::write::FileOptions::default()
+                    .compression_method(zip::CompressionMethod::Deflated)
+                    .compression_level(Some(level as i32));
+                archive
+                    .start_file(ZIP_ENTRY, options)
+                    .map_err(io::Error::other)?;
+                ObjectWriter::Deflate(archive)
+            }
+            Compression::Zstd(level) => ObjectWriter::Zstd(zstd::Encoder::new(file, level)?),
+        })
+    }
+
+    fn finish(self) -> io::Result<File> {
+        match self {
+            ObjectWriter::Plain(file) => Ok(file),
+            ObjectWriter::Deflate(mut archive) => archive.finish().map_err(io::Error::other),
+            ObjectWriter::Zstd(encoder) => encoder.finish(),
+        }
+    }
+}
+
+impl Write for ObjectWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            ObjectWriter::Plain(file) => file.write(buf),
+            ObjectWriter::Deflate(archive) => archive.write(buf),
+            ObjectWriter::Zstd(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            ObjectWriter::Plain(file) => file.flush(),
+            ObjectWriter::Deflate(archive) => archive.flush(),
+            ObjectWriter::Zstd(encoder) => encoder.flush(),
+        }
+    }
+}
+
+impl BackupManifest {
+    pub fn total_size(&self) -> u64 {
+        self.files.iter().map(|file| file.size).sum()
+    }
+}
+
+// Files of a backup whose object is gone or no longer matches the manifest's hash
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct VerifyReport {
+    pub checked_files: usize,
+    pub missing: Vec<String>,
+    pub corrupt: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct GarbageReport {
+    pub removed_objects: usize,
+    pub freed_bytes: u64,
+}
+
+pub(crate) fn manifest_path(game_saves_dir: &Path, save_id: &str) -> PathBuf {
+    game_saves_dir.join(format!("{}{}", save_id, MANIFEST_EXTENSION))
+}
+
+pub(crate) fn is_manifest(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.ends_with(MANIFEST_EXTENSION))
+}
+
+// Backup name (save id) of a manifest file name
+pub(crate) fn save_id_of_manifest(file_name: &str) -> Option<&str> {
+    file_name.strip_suffix(MANIFEST_EXTENSION)
+}
+
+fn object_path(saves_dir: &Path, hash: &str, encoding: Option<Encoding>) -> PathBuf {
+    let name = format!("{}{}", &hash[2..], encoding.map_or("", Encoding::extension));
+    saves_dir.join(OBJECTS_DIR).join(&hash[..2]).join(name)
+}
+
+// Stored copy of the contents with this hash, whatever its compression
+fn find_object(saves_dir: &Path, hash: &str) -> Option<Option<Encoding>> {
+    [None, Some(Encoding::Zstd), Some(Encoding::Deflate)]
+        .into_iter()
+        .find(|encoding| object_path(saves_dir, hash, *encoding).is_file())
+}
+
+// Hash a file while copying it into the store; content already stored (with any compression)
+// is not written again
+fn store_object(
+    saves_dir: &Path,
+    source: &Path,
+    compression: Compression,
+) -> io::Result<(String, u64, Option<Encoding>)> {
+    let objects_dir = saves_dir.join(OBJECTS_DIR);
+    fs::create_dir_all(&objects_dir)?;
+    let temp_path = objects_dir.join(format!("tmp-{}", uuid::Uuid::new_v4()));
+
+    let mut hasher = Sha256::new();
+    let mut size = 0u64;
+    let result = (|| {
+        let mut input = File::open(source)?;
+        let mut output = ObjectWriter::new(File::create(&temp_path)?, compression)?;
+        let mut buffer = vec![0u8; 64 * 1024];
+        loop {
+            let read = input.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+            output.write_all(&buffer[..read])?;
+            size += read as u64;
+        }
+        output.finish()?.sync_all()
+    })();
+    if let Err(e) = result {
+        let _ = fs::remove_file(&temp_path);
+        return Err(e);
+    }
+
+    let hash = format!("{:x}", hasher.finalize());
+    if let Some(existing) = find_object(saves_dir, &hash) {
+        fs::remove_file(&temp_path)?;
+        return Ok((hash, size, existing));
+    }
+    let path = object_path(saves_dir, &hash, compression.encoding());
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(&temp_path, &path)?;
+    Ok((hash, size, compression.encoding()))
+}
+
+fn modified_secs(path: &Path) -> Option<i64> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|since_epoch| since_epoch.as_secs() as i64)
+}
+
+fn hash_file(path: &Path) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+// Whether the files (relative path, source path) hold exactly what the manifest recorded.
+// Files whose size and modification time are unchanged are not hashed again, unless they were
+// modified within the second the backup was taken.
+pub(crate) fn matches_manifest(
+    manifest: &BackupManifest,
+    files: &[(String, PathBuf)],
+) -> io::Result<bool> {
+    if files.len() != manifest.files.len() {
+        return Ok(false);
+    }
+    let created_at = chrono::DateTime::parse_from_rfc3339(&manifest.created_at)
+        .map(|created_at| created_at.timestamp())
+        .ok();
+    let mut files: Vec<&(String, PathBuf)> = files.iter().collect();
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    for ((path, source), recorded) in files.into_iter().zip(&manifest.files) {
+        if *path != recorded.path || fs::metadata(source)?.len() != recorded.size {
+            return Ok(false);
+        }
+        let settled = match (recorded.mtime, created_at) {
+            (Some(mtime), Some(created_at)) => mtime < created_at,
+            _ => false,
+        };
+        if settled && modified_secs(source) == recorded.mtime {
+            continue;
+        }
+        if hash_file(source)? != recorded.sha256 {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+// Store the files (relative path, source path) and write the backup's manifest
+pub(crate) fn create_backup(
+    saves_dir: &Path,
+    manifest_path: &Path,
+    game_id: &str,
+    files: &[(String, PathBuf)],
+    compression: Compression,
+) -> io::Result<BackupManifest> {
+    let _lock = STORE_LOCK
+        .lock()
+        .map_err(|_| io::Error::other("Backup store lock poisoned"))?;
+
+    let mut manifest = BackupManifest {
+        game_id: game_id.to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        files: Vec::with_capacity(files.len()),
+    };
+    for (path, source) in files {
+        let (sha256, size, encoding) = store_object(saves_dir, source, compression)?;
+        manifest.files.push(ManifestFile {
+            path: path.clone(),
+            size,
+            sha256,
+            mtime: modified_secs(source),
+            encoding,
+        });
+    }
+    manifest.files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    if let Some(parent) = manifest_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let data = serde_json::to_vec_pretty(&manifest).map_err(io::Error::other)?;
+    fs::write(manifest_path, data)?;
+    Ok(manifest)
+}
+
+pub(crate) fn read_manifest(path: &Path) -> io::Result<BackupManifest> {
+    let data = fs::read(path)?;
+    serde_json::from_slice(&data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+// Manifest paths come from disk, so reject any that would escape the restore directory
+fn safe_relative_path(path: &str) -> io::Result<PathBuf> {
+    let relative = PathBuf::from(path);
+    if relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        Ok(relative)
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Invalid path in backup manifest: {}", path),
+        ))
+    }
+}
+
+// Open a backed up file for reading, decompressing it as needed
+pub(crate) fn open_object(saves_dir: &Path, file: &ManifestFile) -> io::Result<Box<dyn Read>> {
+    let object = File::open(object_path(saves_dir, &file.sha256, file.encoding))?;
+    Ok(match file.encoding {
+        None => Box::new(object),
+        Some(Encoding::Deflate) => {
+            // An archive entry borrows its archive, so the contents are read up front
+            let mut archive = zip::ZipArchive::new(object).map_err(io::Error::other)?;
+            let mut contents = Vec::with_capacity(file.size as usize);
+            archive
+                .by_name(ZIP_ENTRY)
+                .map_err(io::Error::other)?
+                .read_to_end(&mut contents)?;
+            Box::new(io::Cursor::new(contents))
+        }
+        Some(Encoding::Zstd) => Box::new(zstd::Decoder::new(object)?),
+    })
+}
+
+pub(crate) fn read_object(saves_dir: &Path, file: &ManifestFile) -> io::Result<Vec<u8>> {
+    let mut contents = Vec::with_capacity(file.size as usize);
+    open_object(saves_dir, file)?.read_to_end(&mut contents)?;
+    Ok(contents)
+}
+
+// Copy a backed up file into a temporary file next to `target`, checking its hash and size
+// against the manifest on the way. The target itself is not touched.
+fn stage_object(saves_dir: &Path, file: &ManifestFile, target: &Path) -> io::Result<PathBuf> {
+    let mut input = open_object(saves_dir, file)?;
+    let file_name = target.file_name().unwrap_or_default().to_string_lossy();
+    let temp_path =
+        target.with_file_name(format!(".{}.restore-{}", file_name, uuid::Uuid::new_v4()));
+
+    let mut hasher = Sha256::new();
+    let mut size = 0u64;
+    let result = (|| {
+        let mut output = File::create(&temp_path)?;
+        let mut buffer = vec![0u8; 64 * 1024];
+        loop {
+            let read = input.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+            output.write_all(&buffer[..read])?;
+            size += read as u64;
+        }
+        let hash = format!("{:x}", hasher.finalize());
+        if hash != file.sha256 || size != file.size {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Backed up copy of {} is corrupt", file.path),
+            ));
+        }
+        output.sync_all()
+    })();
+    if let Err(e) = result {
+        let _ = fs::remove_file(&temp_path);
+        return Err(e);
+    }
+    Ok(temp_path)
+}
+
+// Write the manifest's files below `destination`, skipping those `include` rejects. Every file
+// is copied and verified before any existing one is replaced, so a missing or damaged object
+// leaves the destination as it was.
+pub(crate) fn restore_backup<F>(
+    saves_dir: &Path,
+    manifest: &BackupManifest,
+    destination: &Path,
+    include: F,
+) -> io::Result<u64>
+where
+    F: Fn(&Path) -> bool,
+{
+    let mut staged: Vec<(PathBuf, PathBuf)> = Vec::new();
+    let mut total_size = 0u64;
+    let result = (|| {
+        for file in &manifest.files {
+            let relative = safe_relative_path(&file.path)?;
+            if !include(&relative) {
+                continue;
+            }
+            let target = destination.join(&relative);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            staged.push((stage_object(saves_dir, file, &target)?, target));
+            total_size += file.size;
+        }
+        Ok(())
+    })();
+    if let Err(e) = result {
+        for (temp_path, _) in &staged {
+            let _ = fs::remove_file(temp_path);
+        }
+        return Err(e);
+    }
+
+    for (temp_path, target) in staged {
+        fs::rename(&temp_path, &target)?;
+    }
+    Ok(total_size)
+}
+
+fn hash_object(saves_dir: &Path, file: &ManifestFile) -> io::Result<(String, u64)> {
+    let mut hasher = Sha256::new();
+    let size = io::copy(&mut open_object(saves_dir, file)?, &mut hasher)?;
+    Ok((format!("{:x}", hasher.finalize()), size))
+}
+
+// Re-hash every object the manifest references and compare it with the recorded hash and size
+pub(crate) fn verify_backup(
+    saves_dir: &Path,
+    manifest: &BackupManifest,
+) -> io::Result<VerifyReport> {
+    let _lock = STORE_LOCK
+        .lock()
+        .map_err(|_| io::Error::other("Backup store lock poisoned"))?;
+    let mut report = VerifyReport::default();
+    for file in &manifest.files {
+        report.checked_files += 1;
+        if !object_path(saves_dir, &file.sha256, file.encoding).is_file() {
+            report.missing.push(file.path.clone());
+            continue;
+        }
+        match hash_object(saves_dir, file) {
+            Ok((hash, size)) if hash == file.sha256 && size == file.size => {}
+            _ => report.corrupt.push(file.path.clone()),
+        }
+    }
+    Ok(report)
+}
+
+// Remove objects no manifest references any more
+pub(crate) fn collect_garbage(saves_dir: &Path) -> io::Result<GarbageReport> {
+    let _lock = STORE_LOCK
+        .lock()
+        .map_err(|_| io::Error::other("Backup store lock poisoned"))?;
+    let mut report = GarbageReport::default();
+    let objects_dir = saves_dir.join(OBJECTS_DIR);
+    if !objects_dir.is_dir() {
+        return Ok(report);
+    }
+
+    let mut referenced = HashSet::new();
+    for game_dir in fs::read_dir(saves_dir)?.filter_map(Result::ok) {
+        if game_dir.file_name() == OBJECTS_DIR || !game_dir.path().is_dir() {
+            continue;
+        }
+        for entry in fs::read_dir(game_dir.path())?.filter_map(Result::ok) {
+            if !is_manifest(&entry.path()) {
+                continue;
+            }
+            // An unreadable manifest may still reference objects; keep everything
+            let manifest = read_manifest(&entry.path()).map_err(|e| {
+                io::Error::new(
+                    e.kind(),
+                    format!("Failed to read manifest {:?}: {}", entry.path(), e),
+                )
+            })?;
+            referenced.extend(manifest.files.into_iter().map(|file| file.sha256));
+        }
+    }
+
+    for prefix_dir in fs::read_dir(&objects_dir)?.filter_map(Result::ok) {
+        if !prefix_dir.path().is_dir() {
+            continue;
+        }
+        let prefix = prefix_dir.file_name().to_string_lossy().into_owned();
+        for object in fs::read_dir(prefix_dir.path())?.filter_map(Result::ok) {
+            let name = object.file_name().to_string_lossy().into_owned();
+            let hash = format!("{}{}", prefix, name.split('.').next().unwrap_or_default());
+            if referenced.contains(&hash) {
+                continue;
+            }
+            let size = object.metadata().map(|m| m.len()).unwrap_or(0);
+            fs::remove_file(object.path())?;
+            report.removed_objects += 1;
+            report.freed_bytes += size;
+        }
+        let _ = fs::remove_dir(prefix_dir.path()); // Only succeeds once empty
+    }
+
+    println!(
+        "Backup store garbage collection removed {} objects ({} bytes)",
+        report.removed_objects, report.freed_bytes
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_dedup_restore_and_gc() {
+        let root = std::env::temp_dir().join(format!("rogame_store_{}", uuid::Uuid::new_v4()));
+        let saves_dir = root.join("saves");
+        let source = root.join("source");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("slot1.sav"), "same").unwrap();
+        fs::write(source.join("slot2.sav"), "same").unwrap();
+        fs::write(source.join("options.ini"), "old").unwrap();
+
+        let files = |names: &[&str]| -> Vec<(String, PathBuf)> {
+            names
+                .iter()
+                .map(|name| (name.to_string(), source.join(name)))
+                .collect()
+        };
+        let game_dir = saves_dir.join("1245620");
+        let first_path = manifest_path(&game_dir, "backup_1");
+        let first = create_backup(
+            &saves_dir,
+            &first_path,
+            "1245620",
+            &files(&["slot1.sav", "slot2.sav", "options.ini"]),
+            Compression::None,
+        )
+        .unwrap();
+        assert_eq!(first.total_size(), 11);
+        assert_eq!(read_manifest(&first_path).unwrap(), first);
+
+        // Identical contents are stored once, also across backups and compression settings
+        fs::write(source.join("options.ini"), "new").unwrap();
+        let second_path = manifest_path(&game_dir, "backup_2");
+        let second = create_backup(
+            &saves_dir,
+            &second_path,
+            "1245620",
+            &files(&["slot1.sav", "options.ini"]),
+            Compression::Zstd(3),
+        )
+        .unwrap();
+        assert_eq!(second.files[0].encoding, Some(Encoding::Zstd));
+        assert_eq!(second.files[1].encoding, None);
+        assert!(second.files.iter().all(|file| file.mtime.is_some()));
+        let object_count = || {
+            walkdir::WalkDir::new(saves_dir.join(OBJECTS_DIR))
+                .into_iter()
+                .filter_map(Result::ok)
+                .filter(|entry| entry.file_type().is_file())
+                .count()
+        };
+        assert_eq!(object_count(), 3);
+
+        let restored = root.join("restored");
+        restore_backup(&saves_dir, &first, &restored, |path| {
+            path != Path::new("slot2.sav")
+        })
+        .unwrap();
+        assert_eq!(
+            fs::read_to_string(restored.join("options.ini")).unwrap(),
+            "old"
+        );
+        assert!(!restored.join("slot2.sav").exists());
+        restore_backup(&saves_dir, &second, &restored, |_| true).unwrap();
+        assert_eq!(
+            fs::read_to_string(restored.join("options.ini")).unwrap(),
+            "new"
+        );
+
+        // Changes are detected by contents, not by the modification time alone
+        let current = files(&["slot1.sav", "options.ini"]);
+        assert!(matches_manifest(&second, &current).unwrap());
+        assert!(!matches_manifest(&first, &current).unwrap());
+        fs::write(source.join("slot1.sav"), "same").unwrap();
+        assert!(matches_manifest(&second, &current).unwrap());
+        fs::write(source.join("slot1.sav"), "diff").unwrap();
+        assert!(!matches_manifest(&second, &current).unwrap());
+
+        // Damaged and lost objects are reported by verification
+        let report = verify_backup(&saves_dir, &first).unwrap();
+        assert_eq!(report.checked_files, 3);
+        assert!(report.missing.is_empty() && report.corrupt.is_empty());
+        let options = &first.files[0];
+        fs::write(
+            object_path(&saves_dir, &options.sha256, options.encoding),
+            "odl",
+        )
+        .unwrap();
+        let report = verify_backup(&saves_dir, &first).unwrap();
+        assert_eq!(report.corrupt, vec!["options.ini".to_string()]);
+
+        // Dropping the first backup frees only the old options.ini
+        fs::remove_file(&first_path).unwrap();
+        let report = collect_garbage(&saves_dir).unwrap();
+        assert_eq!(report.removed_objects, 1);
+        assert_eq!(report.freed_bytes, 3);
+        assert_eq!(object_count(), 2);
+
+        let options = &second.files[0];
+        fs::remove_file(object_path(&saves_dir, &options.sha256, options.encoding)).unwrap();
+        let report = verify_backup(&saves_dir, &second).unwrap();
+        assert_eq!(report.missing, vec!["options.ini".to_string()]);
+
+        // Restoring a backup with a lost object leaves the existing files alone
+        fs::write(restored.join("options.ini"), "live").unwrap();
+        fs::write(restored.join("slot1.sav"), "live").unwrap();
+        assert!(restore_backup(&saves_dir, &second, &restored, |_| true).is_err());
+        for name in ["options.ini", "slot1.sav"] {
+            assert_eq!(fs::read_to_string(restored.join(name)).unwrap(), "live");
+        }
+        assert_eq!(fs::read_dir(&restored).unwrap().count(), 2);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_deflate_objects() {
+        let root = std::env::temp_dir().join(format!("rogame_deflate_{}", uuid::Uuid::new_v4()));
+        let saves_dir = root.join("saves");
+        let source = root.join("slot1.sav");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(&source, "save ".repeat(100)).unwrap();
+
+        let manifest = create_backup(
+            &saves_dir,
+            &manifest_path(&saves_dir.join("1245620"), "backup_1"),
+            "1245620",
+            &[("slot1.sav".to_string(), source)],
+            Compression::Deflate(6),
+        )
+        .unwrap();
+        let file = &manifest.files[0];
+        assert_eq!(file.encoding, Some(Encoding::Deflate));
+        let object = object_path(&saves_dir, &file.sha256, file.encoding);
+        assert!(fs::metadata(&object).unwrap().len() < file.size);
+        assert_eq!(
+            read_object(&saves_dir, file).unwrap(),
+            "save ".repeat(100).as_bytes()
+        );
+        assert!(verify_backup(&saves_dir, &manifest)
+            .unwrap()
+            .corrupt
+            .is_empty());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
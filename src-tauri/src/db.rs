@@ -49,6 +49,54 @@ pub fn initialize_database(conn: &Connection) -> SqlResult<()> {
         [],
     )?;
 
+    // Add per-game save rules to existing games table if they don't exist (JSON lists of globs)
+    let _ = conn.execute(
+        "ALTER TABLE games ADD COLUMN save_pattern TEXT",
+        [],
+    ); // Ignore error if column already exists
+    let _ = conn.execute(
+        "ALTER TABLE games ADD COLUMN exclude_patterns TEXT",
+        [],
+    ); // Ignore error if column already exists
+
+    // Add per-game auto-backup interval to existing games table if it doesn't exist
+    let _ = conn.execute(
+        "ALTER TABLE games ADD COLUMN backup_interval TEXT",
+        [],
+    ); // Ignore error if column already exists
+
+    // Add per-game save watching to existing games table if it doesn't exist
+    let _ = conn.execute(
+        "ALTER TABLE games ADD COLUMN watch_saves BOOLEAN NOT NULL DEFAULT 0",
+        [],
+    ); // Ignore error if column already exists
+
+    // Add install directory and last play session (Unix ms) to existing games table
+    let _ = conn.execute(
+        "ALTER TABLE games ADD COLUMN install_path TEXT",
+        [],
+    ); // Ignore error if column already exists
+    let _ = conn.execute(
+        "ALTER TABLE games ADD COLUMN last_session_start INTEGER",
+        [],
+    ); // Ignore error if column already exists
+    let _ = conn.execute(
+        "ALTER TABLE games ADD COLUMN last_session_end INTEGER",
+        [],
+    ); // Ignore error if column already exists
+
+    // Add per-game retention policy (JSON) to existing games table if it doesn't exist
+    let _ = conn.execute(
+        "ALTER TABLE games ADD COLUMN retention_policy TEXT",
+        [],
+    ); // Ignore error if column already exists
+
+    // Add the game database entry a game was matched to, to existing games table
+    let _ = conn.execute(
+        "ALTER TABLE games ADD COLUMN database_id TEXT",
+        [],
+    ); // Ignore error if column already exists
+
     // Create save_files table
     conn.execute(
         "CREATE TABLE IF NOT EXISTS save_files (
@@ -60,6 +108,14 @@ pub fn initialize_database(conn: &Connection) -> SqlResult<()> {
             size_bytes INTEGER NOT NULL,
             file_path TEXT NOT NULL,
             cloud TEXT,
+            steam_account_id TEXT,
+            steam_persona_name TEXT,
+            verification_status TEXT,
+            verified_at TEXT,
+            pinned BOOLEAN NOT NULL DEFAULT 0,
+            tags TEXT,
+            label TEXT,
+            notes TEXT,
             FOREIGN KEY (game_id) REFERENCES games(id)
         )",
         [],
@@ -71,6 +127,46 @@ pub fn initialize_database(conn: &Connection) -> SqlResult<()> {
         [],
     ); // Ignore error if column already exists
 
+    // Add Steam account columns to existing save_files table if they don't exist
+    let _ = conn.execute(
+        "ALTER TABLE save_files ADD COLUMN steam_account_id TEXT",
+        [],
+    ); // Ignore error if column already exists
+    let _ = conn.execute(
+        "ALTER TABLE save_files ADD COLUMN steam_persona_name TEXT",
+        [],
+    ); // Ignore error if column already exists
+
+    // Add backup verification columns to existing save_files table if they don't exist
+    let _ = conn.execute(
+        "ALTER TABLE save_files ADD COLUMN verification_status TEXT",
+        [],
+    ); // Ignore error if column already exists
+    let _ = conn.execute(
+        "ALTER TABLE save_files ADD COLUMN verified_at TEXT",
+        [],
+    ); // Ignore error if column already exists
+
+    // Add pinned column to existing save_files table if it doesn't exist
+    let _ = conn.execute(
+        "ALTER TABLE save_files ADD COLUMN pinned BOOLEAN NOT NULL DEFAULT 0",
+        [],
+    ); // Ignore error if column already exists
+
+    // Add backup tags (JSON list), label and notes to existing save_files table
+    let _ = conn.execute(
+        "ALTER TABLE save_files ADD COLUMN tags TEXT",
+        [],
+    ); // Ignore error if column already exists
+    let _ = conn.execute(
+        "ALTER TABLE save_files ADD COLUMN label TEXT",
+        [],
+    ); // Ignore error if column already exists
+    let _ = conn.execute(
+        "ALTER TABLE save_files ADD COLUMN notes TEXT",
+        [],
+    ); // Ignore error if column already exists
+
     // Create settings table
     conn.execute(
         "CREATE TABLE IF NOT EXISTS settings (
@@ -118,6 +214,51 @@ pub fn initialize_database(conn: &Connection) -> SqlResult<()> {
         [],
     ); // Ignore error if column already exists
 
+    // Create scan_cache table for incremental game scans
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS scan_cache (
+            kind TEXT NOT NULL,
+            path TEXT NOT NULL,
+            mtime INTEGER NOT NULL,
+            data TEXT NOT NULL,
+            PRIMARY KEY (kind, path)
+        )",
+        [],
+    )?;
+
+    // Create scan_results table holding the previous scan, to report what changed
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS scan_results (
+            game_id TEXT PRIMARY KEY,
+            data TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    // Create game_store_ids table mapping each store's id of a game to one library game
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS game_store_ids (
+            store TEXT NOT NULL,
+            store_game_id TEXT NOT NULL,
+            game_id TEXT NOT NULL,
+            PRIMARY KEY (store, store_game_id),
+            FOREIGN KEY (game_id) REFERENCES games(id)
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_game_store_ids_game_id ON game_store_ids (game_id)",
+        [],
+    )?;
+
+    // Steam games added before store ids existed are keyed by their app id
+    conn.execute(
+        "INSERT OR IGNORE INTO game_store_ids (store, store_game_id, game_id)
+         SELECT 'steam', id, id FROM games
+         WHERE platform = 'Steam' AND id NOT GLOB '*[^0-9]*'",
+        [],
+    )?;
+
     Ok(())
 }
 
@@ -0,0 +1,457 @@
+use crate::ludusavi;
+use crate::save_paths::GameEntry;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+// The save game database: entries from a local Ludusavi manifest, replaced by the bundled
+// save_game_location.json, with the user's overrides (save_game_overrides.json in the rogame
+// config dir) deep-merged over them. Overrides are keyed by game id like the bundled file;
+// a `null` entry hides a game.
+
+const OVERRIDES_FILE: &str = "save_game_overrides.json";
+
+// Bundled copy used when the resource file can't be read
+const EMBEDDED_CONFIG: &str = include_str!("save_game_location.json");
+
+pub(crate) type GameDatabase = HashMap<String, GameEntry>;
+
+// Merged database, loaded on first use and dropped whenever the overrides change
+static GAME_DATABASE: Lazy<RwLock<Option<Arc<GameDatabase>>>> = Lazy::new(|| RwLock::new(None));
+
+// Raw JSON entries by game id, before they are parsed into a `GameDatabase`
+type RawEntries = Map<String, Value>;
+
+// Entries the overrides apply to, loaded on first use and dropped when the Ludusavi manifest
+// changes
+static BASE_ENTRIES: Lazy<RwLock<Option<Arc<RawEntries>>>> = Lazy::new(|| RwLock::new(None));
+
+// Helper function to get save game config path
+fn get_save_config_path() -> PathBuf {
+    if cfg!(debug_assertions) {
+        // During development, use the source file
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/save_game_location.json")
+    } else {
+        // In production, load from resources
+        if let Ok(exe_path) = std::env::current_exe() {
+            if let Some(exe_dir) = exe_path.parent() {
+                // Try multiple possible locations based on platform
+                let mut resource_paths = vec![];
+
+                #[cfg(target_os = "macos")]
+                {
+                    // On macOS, resources are in Contents/Resources
+                    if let Some(contents_dir) = exe_dir.parent() {
+                        if let Some(_app_dir) = contents_dir.parent() {
+                            resource_paths.push(
+                                contents_dir
+                                    .join("Resources")
+                                    .join("save_game_location.json"),
+                            );
+                        }
+                    }
+                }
+
+                #[cfg(target_os = "windows")]
+                {
+                    // On Windows, resources are typically in the same directory as the exe
+                    resource_paths.push(exe_dir.join("save_game_location.json"));
+                    resource_paths.push(exe_dir.join("resources").join("save_game_location.json"));
+                }
+
+                #[cfg(target_os = "linux")]
+                {
+                    // On Linux, could be in various locations
+                    resource_paths.push(exe_dir.join("save_game_location.json"));
+                    resource_paths.push(exe_dir.join("resources").join("save_game_location.json"));
+                    // AppImage structure
+                    resource_paths.push(
+                        exe_dir
+                            .join("../lib/rogame")
+                            .join("save_game_location.json"),
+                    );
+                }
+
+                // Common fallback paths
+                resource_paths.push(
+                    exe_dir
+                        .join("_up_")
+                        .join("Resources")
+                        .join("save_game_location.json"),
+                );
+
+                println!("Searching for save_game_location.json in production mode:");
+                for path in &resource_paths {
+                    println!("  Checking: {:?} - exists: {}", path, path.exists());
+                    if path.exists() {
+                        println!("  Found config at: {:?}", path);
+                        return path.clone();
+                    }
+                }
+
+                println!(
+                    "WARNING: Could not find save_game_location.json in any expected location!"
+                );
+            }
+        }
+
+        // Fallback - this will likely fail but provides error info
+        println!("FALLBACK: Using relative path save_game_location.json");
+        PathBuf::from("save_game_location.json")
+    }
+}
+
+fn get_overrides_path() -> Result<PathBuf, String> {
+    Ok(dirs::config_local_dir()
+        .ok_or_else(|| "Failed to get config directory".to_string())?
+        .join("rogame")
+        .join(OVERRIDES_FILE))
+}
+
+fn read_bundled() -> Result<Map<String, Value>, String> {
+    let config_path = get_save_config_path();
+    println!("Loading save game config from: {:?}", config_path);
+
+    let content = match fs::read_to_string(&config_path) {
+        Ok(content) => content,
+        Err(e) => {
+            println!("Error reading config file: {}", e);
+            println!("Using embedded config as fallback...");
+            EMBEDDED_CONFIG.to_string()
+        }
+    };
+
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse save game configuration: {}", e))
+}
+
+// Entries the overrides apply to: the Ludusavi manifest with the bundled file on top
+fn read_base() -> Result<Arc<RawEntries>, String> {
+    if let Some(base) = BASE_ENTRIES.read().ok().and_then(|cached| cached.clone()) {
+        return Ok(base);
+    }
+
+    let mut base = Map::new();
+    if let Some(games) = ludusavi::load_manifest() {
+        for (game_id, entry) in games.iter() {
+            if let Ok(value) = serde_json::to_value(entry) {
+                base.insert(game_id.clone(), value);
+            }
+        }
+    }
+
+    // Hand-curated entries replace Ludusavi's
+    base.extend(read_bundled()?);
+    let base = Arc::new(base);
+
+    if let Ok(mut cached) = BASE_ENTRIES.write() {
+        *cached = Some(base.clone());
+    }
+    Ok(base)
+}
+
+fn read_overrides() -> Result<Map<String, Value>, String> {
+    let path = get_overrides_path()?;
+    if !path.exists() {
+        return Ok(Map::new());
+    }
+
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read overrides file: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse overrides file: {}", e))
+}
+
+fn write_overrides(overrides: &Map<String, Value>) -> Result<(), String> {
+    let path = get_overrides_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+
+    let content = serde_json::to_string_pretty(overrides)
+        .map_err(|e| format!("Failed to serialize overrides: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write overrides file: {}", e))?;
+
+    if let Ok(mut cached) = GAME_DATABASE.write() {
+        *cached = None;
+    }
+    Ok(())
+}
+
+// Merge `overlay` into `base`: objects are merged key by key, `null` removes a key and
+// anything else replaces the base value
+fn deep_merge(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                if value.is_null() {
+                    base.remove(&key);
+                } else if let Some(existing) = base.get_mut(&key) {
+                    deep_merge(existing, value);
+                } else {
+                    base.insert(key, value);
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+// Merge the overrides over the bundled entries. Entries that don't parse are skipped so
+// one bad override doesn't hide the whole database.
+fn merge_database(bundled: Map<String, Value>, overrides: Map<String, Value>) -> GameDatabase {
+    let mut merged = Value::Object(bundled);
+    deep_merge(&mut merged, Value::Object(overrides));
+
+    let Value::Object(merged) = merged else {
+        return HashMap::new();
+    };
+
+    merged
+        .into_iter()
+        .filter_map(|(game_id, value)| match serde_json::from_value(value) {
+            Ok(entry) => Some((game_id, entry)),
+            Err(e) => {
+                println!("Skipping invalid save game entry {}: {}", game_id, e);
+                None
+            }
+        })
+        .collect()
+}
+
+// The merged save game database. Loaded once and shared until the overrides change.
+pub(crate) fn load() -> Result<Arc<GameDatabase>, String> {
+    if let Some(database) = GAME_DATABASE.read().ok().and_then(|cached| cached.clone()) {
+        return Ok(database);
+    }
+
+    let base = read_base()?;
+    let overrides = read_overrides().unwrap_or_else(|e| {
+        println!("Ignoring save game overrides: {}", e);
+        Map::new()
+    });
+    let database = Arc::new(merge_database((*base).clone(), overrides));
+    println!("Loaded {} save game entries", database.len());
+
+    if let Ok(mut cached) = GAME_DATABASE.write() {
+        *cached = Some(database.clone());
+    }
+    Ok(database)
+}
+
+// Drop the cached database after the Ludusavi manifest changed
+pub(crate) fn invalidate() {
+    if let Ok(mut cached) = BASE_ENTRIES.write() {
+        *cached = None;
+    }
+    if let Ok(mut cached) = GAME_DATABASE.write() {
+        *cached = None;
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct GameDatabaseEntry {
+    pub game_id: String,
+    pub entry: GameEntry,
+    // Whether the entry comes from (or changes) the user's overrides
+    pub overridden: bool,
+    pub bundled: bool,
+    pub ludusavi: bool,
+}
+
+// List the merged database, marking which entries the user has overridden
+#[tauri::command]
+pub async fn get_game_database() -> Result<Vec<GameDatabaseEntry>, String> {
+    let bundled = read_bundled()?;
+    let ludusavi = ludusavi::load_manifest();
+    let overrides = read_overrides()?;
+
+    let mut entries: Vec<GameDatabaseEntry> = load()?
+        .iter()
+        .map(|(game_id, entry)| GameDatabaseEntry {
+            game_id: game_id.clone(),
+            entry: entry.clone(),
+            overridden: overrides.contains_key(game_id),
+            bundled: bundled.contains_key(game_id),
+            ludusavi: ludusavi
+                .as_ref()
+                .is_some_and(|games| games.contains_key(game_id)),
+        })
+        .collect();
+    entries.sort_by(|a, b| {
+        a.entry
+            .name
+            .to_lowercase()
+            .cmp(&b.entry.name.to_lowercase())
+    });
+
+    Ok(entries)
+}
+
+// Create or edit a game entry. `entry` may be partial; it is merged over the existing
+// override and the result must form a complete entry.
+#[tauri::command]
+pub async fn save_game_database_entry(game_id: String, entry: Value) -> Result<GameEntry, String> {
+    if game_id.trim().is_empty() {
+        return Err("Game id cannot be empty".to_string());
+    }
+    if !entry.is_object() {
+        return Err("Game entry must be an object".to_string());
+    }
+
+    let base = read_base()?;
+    let mut overrides = read_overrides()?;
+
+    let mut override_entry = overrides
+        .remove(&game_id)
+        .filter(|value| value.is_object())
+        .unwrap_or_else(|| Value::Object(Map::new()));
+    deep_merge(&mut override_entry, entry);
+
+    let merged = merge_entry(&game_id, base.get(&game_id), override_entry.clone())?;
+
+    println!("Saving save game override for {}", game_id);
+    overrides.insert(game_id, override_entry);
+    write_overrides(&overrides)?;
+
+    Ok(merged)
+}
+
+// Apply an override to the entry it changes, if any. The result must be a complete entry for
+// the game it is keyed by, so an override can't point one game at another's saves.
+fn merge_entry(
+    game_id: &str,
+    base_entry: Option<&Value>,
+    override_entry: Value,
+) -> Result<GameEntry, String> {
+    let mut merged = base_entry
+        .cloned()
+        .unwrap_or_else(|| Value::Object(Map::new()));
+    deep_merge(&mut merged, override_entry);
+    let merged: GameEntry =
+        serde_json::from_value(merged).map_err(|e| format!("Invalid save game entry: {}", e))?;
+
+    if !merged.steam_id.is_empty() && merged.steam_id != game_id {
+        return Err(format!(
+            "Save game entry for '{}' has the Steam id of another game: {}",
+            game_id, merged.steam_id
+        ));
+    }
+    Ok(merged)
+}
+
+// Delete a game entry. User-created entries are removed; bundled and Ludusavi ones are hidden.
+#[tauri::command]
+pub async fn delete_game_database_entry(game_id: String) -> Result<(), String> {
+    let base = read_base()?;
+    let mut overrides = read_overrides()?;
+
+    if base.contains_key(&game_id) {
+        println!("Hiding save game entry {}", game_id);
+        overrides.insert(game_id, Value::Null);
+    } else if overrides.remove(&game_id).is_none() {
+        return Err(format!(
+            "Game '{}' not found in save game database",
+            game_id
+        ));
+    }
+
+    write_overrides(&overrides)
+}
+
+// Drop the user's changes to a game entry, going back to the bundled or Ludusavi one
+#[tauri::command]
+pub async fn reset_game_database_entry(game_id: String) -> Result<bool, String> {
+    let mut overrides = read_overrides()?;
+    if overrides.remove(&game_id).is_none() {
+        return Ok(false);
+    }
+
+    println!("Resetting save game entry {}", game_id);
+    write_overrides(&overrides)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_merge_database() {
+        let bundled = json!({
+            "1245620": {
+                "steam_id": "1245620",
+                "name": "Elden Ring",
+                "save_locations": {
+                    "windows": "%APPDATA%/EldenRing",
+                    "linux": "~/.local/share/Steam/userdata/*/1245620/remote"
+                },
+                "save_pattern": ["*"]
+            },
+            "3489700": {
+                "steam_id": "3489700",
+                "name": "Stellar Blade",
+                "save_locations": { "linux": "~/.local/share/Steam/userdata/*/3489700/remote" },
+                "save_pattern": ["*"]
+            }
+        });
+        let overrides = json!({
+            "1245620": {
+                "save_locations": { "linux": ["<xdgData>/EldenRing", "<home>/EldenRing"] },
+                "exclude": ["*.bak"]
+            },
+            "3489700": null,
+            "custom": {
+                "steam_id": "custom",
+                "name": "My Game",
+                "save_locations": { "linux": "<home>/.mygame" }
+            },
+            "broken": { "name": "Missing fields" }
+        });
+
+        let Value::Object(bundled) = bundled else {
+            unreachable!()
+        };
+        let Value::Object(overrides) = overrides else {
+            unreachable!()
+        };
+        let database = merge_database(bundled, overrides);
+
+        let mut ids: Vec<&String> = database.keys().collect();
+        ids.sort();
+        assert_eq!(ids, vec!["1245620", "custom"]);
+
+        // Objects merge key by key, lists replace
+        let elden_ring = &database["1245620"];
+        assert_eq!(elden_ring.name, "Elden Ring");
+        assert_eq!(elden_ring.save_locations.windows.len(), 1);
+        assert_eq!(elden_ring.save_locations.linux.len(), 2);
+        assert_eq!(elden_ring.save_locations.linux[1].path, "<home>/EldenRing");
+        assert_eq!(elden_ring.exclude, vec!["*.bak".to_string()]);
+
+        assert_eq!(database["custom"].save_pattern, vec!["*".to_string()]);
+    }
+
+    #[test]
+    fn test_merge_entry() {
+        let base = json!({
+            "steam_id": "1245620",
+            "name": "Elden Ring",
+            "save_locations": { "linux": "<home>/EldenRing" }
+        });
+
+        let merged = merge_entry("1245620", Some(&base), json!({ "exclude": ["*.bak"] }));
+        assert_eq!(merged.unwrap().exclude, vec!["*.bak".to_string()]);
+
+        // An entry keyed by one game may not carry another game's Steam id
+        assert!(merge_entry("1245620", Some(&base), json!({ "steam_id": "3489700" })).is_err());
+        assert!(merge_entry("custom", None, base.clone()).is_err());
+
+        assert!(merge_entry("custom", None, json!({ "name": "Missing fields" })).is_err());
+    }
+}
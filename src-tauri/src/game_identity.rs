@@ -0,0 +1,453 @@
+use crate::db;
+use crate::game_database;
+use crate::save_manager;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+// A library game can be owned on several stores. Each store's id for the game (Steam app id,
+// Epic AppName, GOG id, ...) maps to one record in the games table, so detections of the same
+// title on different stores update a single game and share its backups.
+
+// Stores a game id can belong to; "steam_shortcut" covers non-Steam games added to Steam, by
+// their shortcut app id, and "manual" games added by hand
+const STORES: &[&str] = &["steam", "steam_shortcut", "epic", "gog", "lutris", "manual"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct StoreId {
+    pub store: String,
+    pub id: String,
+}
+
+impl StoreId {
+    pub fn new(store: &str, id: impl Into<String>) -> Self {
+        Self {
+            store: store.to_string(),
+            id: id.into(),
+        }
+    }
+}
+
+// Find the library game one of the store ids belongs to
+pub(crate) fn find_game_id(
+    conn: &Connection,
+    store_ids: &[StoreId],
+) -> Result<Option<String>, String> {
+    for store_id in store_ids {
+        let game_id = conn
+            .query_row(
+                "SELECT game_id FROM game_store_ids WHERE store = ?1 AND store_game_id = ?2",
+                params![store_id.store, store_id.id],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()
+            .map_err(|e| format!("Failed to look up store id: {}", e))?;
+        if game_id.is_some() {
+            return Ok(game_id);
+        }
+    }
+    Ok(None)
+}
+
+// Point the store ids at a game, moving them away from any game they belonged to before
+pub(crate) fn link_store_ids(
+    conn: &Connection,
+    game_id: &str,
+    store_ids: &[StoreId],
+) -> Result<(), String> {
+    for store_id in store_ids {
+        conn.execute(
+            "INSERT OR REPLACE INTO game_store_ids (store, store_game_id, game_id) VALUES (?1, ?2, ?3)",
+            params![store_id.store, store_id.id, game_id],
+        )
+        .map_err(|e| format!("Failed to save store id: {}", e))?;
+    }
+    Ok(())
+}
+
+pub(crate) fn store_ids_for_game(conn: &Connection, game_id: &str) -> Result<Vec<StoreId>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT store, store_game_id FROM game_store_ids WHERE game_id = ?1
+             ORDER BY store, store_game_id",
+        )
+        .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+    let store_ids = stmt
+        .query_map(params![game_id], |row| {
+            Ok(StoreId {
+                store: row.get(0)?,
+                id: row.get(1)?,
+            })
+        })
+        .map_err(|e| format!("Failed to query store ids: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to collect store ids: {}", e))?;
+
+    Ok(store_ids)
+}
+
+pub(crate) fn remove_store_ids(conn: &Connection, game_id: &str) -> Result<(), String> {
+    conn.execute(
+        "DELETE FROM game_store_ids WHERE game_id = ?1",
+        params![game_id],
+    )
+    .map_err(|e| format!("Failed to delete store ids: {}", e))?;
+    Ok(())
+}
+
+// Store prefixes of the ids games from other stores were added under before database ids
+// were stored on the games row (`epic_<steam app id>`)
+const LEGACY_ID_PREFIXES: &[&str] = &["epic_", "gog_", "lutris_"];
+
+// Settings key set once games added before `games.database_id` existed have been linked
+const LEGACY_IDS_MIGRATED: &str = "legacy_game_ids_migrated";
+
+// Record the game database entry of games added before `games.database_id` existed, and merge
+// games added under a legacy store-prefixed id into the game stored under the entry's own id.
+// Runs once; it is retried on the next start if the game database can't be loaded.
+pub(crate) async fn migrate_legacy_game_ids() {
+    let migrated = db::execute_blocking(|conn| {
+        conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM settings WHERE key = ?1)",
+            params![LEGACY_IDS_MIGRATED],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to read settings: {}", e))
+    })
+    .await;
+    if migrated != Ok(false) {
+        return;
+    }
+
+    let database = match tokio::task::spawn_blocking(game_database::load).await {
+        Ok(Ok(database)) => database,
+        _ => {
+            println!("Game database unavailable, not linking games to their entries");
+            return;
+        }
+    };
+    let saves_dir = match save_manager::get_saves_directory() {
+        Ok(saves_dir) => saves_dir,
+        Err(e) => {
+            println!("Not linking games to their entries: {}", e.message);
+            return;
+        }
+    };
+    let result = db::execute_blocking(move |conn| {
+        link_legacy_game_ids(conn, &saves_dir, |id| database.contains_key(id))?;
+        conn.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, '1')",
+            params![LEGACY_IDS_MIGRATED],
+        )
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+        Ok(())
+    })
+    .await;
+    if let Err(e) = result {
+        println!("Failed to link games to their game database entries: {}", e);
+    }
+}
+
+fn link_legacy_game_ids(
+    conn: &Connection,
+    saves_dir: &Path,
+    is_database_id: impl Fn(&str) -> bool,
+) -> Result<(), String> {
+    let game_ids: Vec<String> = {
+        let mut stmt = conn
+            .prepare("SELECT id FROM games WHERE database_id IS NULL")
+            .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+        let game_ids = stmt
+            .query_map([], |row| row.get(0))
+            .map_err(|e| format!("Failed to query games: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to collect games: {}", e))?;
+        game_ids
+    };
+
+    for game_id in game_ids {
+        let database_id = if is_database_id(&game_id) {
+            game_id.clone()
+        } else {
+            let legacy_id = LEGACY_ID_PREFIXES
+                .iter()
+                .find_map(|prefix| game_id.strip_prefix(prefix))
+                .filter(|id| is_database_id(id));
+            match legacy_id {
+                Some(id) => id.to_string(),
+                None => continue,
+            }
+        };
+        conn.execute(
+            "UPDATE games SET database_id = ?1 WHERE id = ?2",
+            params![database_id, game_id],
+        )
+        .map_err(|e| format!("Failed to link game to its database entry: {}", e))?;
+
+        let duplicate_of = conn
+            .query_row(
+                "SELECT id FROM games WHERE id = ?1 AND id != ?2",
+                params![database_id, game_id],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()
+            .map_err(|e| format!("Failed to look up game: {}", e))?;
+        if let Some(into) = duplicate_of {
+            println!("Merging game {} into {}", game_id, into);
+            if let Err(e) = merge_games(conn, saves_dir, &game_id, &into) {
+                println!("Failed to merge game {} into {}: {}", game_id, into, e);
+            }
+        }
+    }
+    Ok(())
+}
+
+// Merge a game into another: its backups, community saves and store ids move to the other
+// game, and the game itself is removed
+fn merge_games(conn: &Connection, saves_dir: &Path, from: &str, into: &str) -> Result<(), String> {
+    let from_dir = saves_dir.join(from);
+    let into_dir = saves_dir.join(into);
+
+    // Backup names are timestamps, so they rarely clash; nothing is moved when one does
+    if from_dir.is_dir() {
+        let entries: Vec<fs::DirEntry> = fs::read_dir(&from_dir)
+            .map_err(|e| format!("Failed to read {:?}: {}", from_dir, e))?
+            .filter_map(Result::ok)
+            .collect();
+        if let Some(entry) = entries
+            .iter()
+            .find(|entry| into_dir.join(entry.file_name()).exists())
+        {
+            return Err(format!(
+                "Both games have a backup named {}",
+                entry.file_name().to_string_lossy()
+            ));
+        }
+        fs::create_dir_all(&into_dir)
+            .map_err(|e| format!("Failed to create {:?}: {}", into_dir, e))?;
+        for entry in entries {
+            fs::rename(entry.path(), into_dir.join(entry.file_name()))
+                .map_err(|e| format!("Failed to move {:?}: {}", entry.path(), e))?;
+        }
+        let _ = fs::remove_dir(&from_dir);
+    }
+    let rebase = |path: Option<String>| -> Option<String> {
+        let path = path?;
+        Some(match Path::new(&path).strip_prefix(&from_dir) {
+            Ok(rest) => into_dir.join(rest).to_string_lossy().into_owned(),
+            Err(_) => path,
+        })
+    };
+
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let save_files: Vec<(String, Option<String>)> = {
+        let mut stmt = tx
+            .prepare("SELECT id, file_path FROM save_files WHERE game_id = ?1")
+            .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+        let save_files = stmt
+            .query_map(params![from], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| format!("Failed to query save files: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to collect save files: {}", e))?;
+        save_files
+    };
+    for (id, file_path) in save_files {
+        tx.execute(
+            "UPDATE save_files SET game_id = ?1, file_path = ?2 WHERE id = ?3",
+            params![into, rebase(file_path), id],
+        )
+        .map_err(|e| format!("Failed to move save file: {}", e))?;
+    }
+    let community_saves: Vec<(String, Option<String>, Option<String>)> = {
+        let mut stmt = tx
+            .prepare("SELECT id, local_path, zip_path FROM community_saves WHERE game_id = ?1")
+            .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+        let community_saves = stmt
+            .query_map(params![from], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })
+            .map_err(|e| format!("Failed to query community saves: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to collect community saves: {}", e))?;
+        community_saves
+    };
+    for (id, local_path, zip_path) in community_saves {
+        tx.execute(
+            "UPDATE community_saves SET game_id = ?1, local_path = ?2, zip_path = ?3
+             WHERE id = ?4",
+            params![into, rebase(local_path), rebase(zip_path), id],
+        )
+        .map_err(|e| format!("Failed to move community save: {}", e))?;
+    }
+    tx.execute(
+        "UPDATE game_store_ids SET game_id = ?1 WHERE game_id = ?2",
+        params![into, from],
+    )
+    .map_err(|e| format!("Failed to move store ids: {}", e))?;
+    tx.execute(
+        "UPDATE games SET is_favorite = is_favorite OR (SELECT is_favorite FROM games WHERE id = ?2)
+         WHERE id = ?1",
+        params![into, from],
+    )
+    .map_err(|e| format!("Failed to update game: {}", e))?;
+    tx.execute("DELETE FROM games WHERE id = ?1", params![from])
+        .map_err(|e| format!("Failed to delete game: {}", e))?;
+    tx.commit()
+        .map_err(|e| format!("Failed to merge games: {}", e))?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_game_store_ids(game_id: String) -> Result<Vec<StoreId>, String> {
+    db::execute_blocking(move |conn| store_ids_for_game(conn, &game_id)).await
+}
+
+// Attach a store id to a game, e.g. to merge a game detected on another store into it
+#[tauri::command]
+pub async fn link_game_store_id(game_id: String, store: String, id: String) -> Result<(), String> {
+    if !STORES.contains(&store.as_str()) {
+        return Err(format!("Unknown store: {}", store));
+    }
+    if id.trim().is_empty() {
+        return Err("Store id must not be empty".to_string());
+    }
+
+    db::execute_blocking(move |conn| {
+        println!("Linking {} id {} to game {}", store, id, game_id);
+        link_store_ids(conn, &game_id, &[StoreId::new(&store, id)])
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn unlink_game_store_id(store: String, id: String) -> Result<(), String> {
+    db::execute_blocking(move |conn| {
+        conn.execute(
+            "DELETE FROM game_store_ids WHERE store = ?1 AND store_game_id = ?2",
+            params![store, id],
+        )
+        .map_err(|e| format!("Failed to delete store id: {}", e))?;
+        Ok(())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_store_id_mapping() {
+        let conn = Connection::open_in_memory().unwrap();
+        db::initialize_database(&conn).unwrap();
+        for game_id in ["1245620", "epic_1245620"] {
+            conn.execute(
+                "INSERT INTO games (id, title, cover_image, platform, last_played, save_count,
+                 size, status, category, is_favorite, save_location)
+                 VALUES (?1, 'Elden Ring', '', 'Steam', 'Never', 0, '0B', 'no_saves', 'Action',
+                 0, '')",
+                params![game_id],
+            )
+            .unwrap();
+        }
+
+        let steam = StoreId::new("steam", "1245620");
+        let epic = StoreId::new("epic", "a1b2c3");
+        assert_eq!(
+            find_game_id(&conn, std::slice::from_ref(&steam)).unwrap(),
+            None
+        );
+
+        link_store_ids(&conn, "1245620", std::slice::from_ref(&steam)).unwrap();
+        link_store_ids(&conn, "1245620", std::slice::from_ref(&epic)).unwrap();
+        assert_eq!(
+            find_game_id(&conn, &[StoreId::new("gog", "1"), epic.clone()]).unwrap(),
+            Some("1245620".to_string())
+        );
+        assert_eq!(
+            store_ids_for_game(&conn, "1245620").unwrap(),
+            vec![epic.clone(), steam.clone()]
+        );
+
+        // Linking an id again moves it to the other game
+        link_store_ids(&conn, "epic_1245620", std::slice::from_ref(&epic)).unwrap();
+        assert_eq!(store_ids_for_game(&conn, "1245620").unwrap(), vec![steam]);
+
+        remove_store_ids(&conn, "epic_1245620").unwrap();
+        assert_eq!(find_game_id(&conn, &[epic]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_link_legacy_game_ids() {
+        let conn = Connection::open_in_memory().unwrap();
+        db::initialize_database(&conn).unwrap();
+        for game_id in ["1245620", "epic_1245620", "504230", "gog_1207658924"] {
+            conn.execute(
+                "INSERT INTO games (id, title, cover_image, platform, last_played, save_count,
+                 size, status, category, is_favorite, save_location)
+                 VALUES (?1, 'Game', '', 'Steam', 'Never', 0, '0B', 'no_saves', 'Action', ?2, '')",
+                params![game_id, game_id == "epic_1245620"],
+            )
+            .unwrap();
+        }
+
+        let saves_dir =
+            std::env::temp_dir().join(format!("rogame_legacy_{}", uuid::Uuid::new_v4()));
+        let backup = saves_dir
+            .join("epic_1245620")
+            .join("backup_20240101_000000");
+        fs::create_dir_all(&backup).unwrap();
+        fs::write(backup.join("slot1.sav"), "save").unwrap();
+        conn.execute(
+            "INSERT INTO save_files (id, game_id, file_name, created_at, modified_at, size_bytes,
+             file_path)
+             VALUES ('backup_20240101_000000', 'epic_1245620', 'backup_20240101_000000', '', '',
+             4, ?1)",
+            params![backup.to_string_lossy()],
+        )
+        .unwrap();
+        link_store_ids(&conn, "epic_1245620", &[StoreId::new("epic", "a1b2c3")]).unwrap();
+
+        link_legacy_game_ids(&conn, &saves_dir, |id| ["1245620", "504230"].contains(&id)).unwrap();
+
+        let game_ids: Vec<(String, Option<String>, bool)> = conn
+            .prepare("SELECT id, database_id, is_favorite FROM games ORDER BY id")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            game_ids,
+            vec![
+                ("1245620".to_string(), Some("1245620".to_string()), true),
+                ("504230".to_string(), Some("504230".to_string()), false),
+                ("gog_1207658924".to_string(), None, false),
+            ]
+        );
+
+        // The merged game's backups moved along with their records
+        let moved = saves_dir.join("1245620").join("backup_20240101_000000");
+        assert!(moved.join("slot1.sav").exists());
+        assert!(!saves_dir.join("epic_1245620").exists());
+        let (game_id, file_path): (String, String) = conn
+            .query_row(
+                "SELECT game_id, file_path FROM save_files WHERE id = 'backup_20240101_000000'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(game_id, "1245620");
+        assert_eq!(file_path, moved.to_string_lossy());
+        assert_eq!(
+            find_game_id(&conn, &[StoreId::new("epic", "a1b2c3")]).unwrap(),
+            Some("1245620".to_string())
+        );
+
+        fs::remove_dir_all(&saves_dir).unwrap();
+    }
+}
@@ -1,70 +1,29 @@
 use glob::glob;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, fs, path::PathBuf};
+use once_cell::sync::Lazy;
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex,
+    },
+};
+use tauri::{AppHandle, Emitter};
 use walkdir::WalkDir;
+use crate::game_database;
+use crate::game_identity::StoreId;
+use crate::heroic;
+use crate::lutris;
+use crate::save_paths::{self, GameEntry, ResolveContext, ResolvedLocation};
+use crate::scan_cache::{self, ScanCache, ScanChanges};
 use crate::security::{safe_join_path, validate_path_component};
+#[cfg(target_os = "linux")]
+use crate::proton;
+use crate::vdf;
 
-// Helper function to get save game config path
-fn get_save_config_path() -> PathBuf {
-    if cfg!(debug_assertions) {
-        // During development, use the source file
-        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/save_game_location.json")
-    } else {
-        // In production, load from resources
-        if let Ok(exe_path) = std::env::current_exe() {
-            if let Some(exe_dir) = exe_path.parent() {
-                // Try multiple possible locations based on platform
-                let mut resource_paths = vec![];
-                
-                #[cfg(target_os = "macos")]
-                {
-                    // On macOS, resources are in Contents/Resources
-                    if let Some(contents_dir) = exe_dir.parent() {
-                        if let Some(_app_dir) = contents_dir.parent() {
-                            resource_paths.push(contents_dir.join("Resources").join("save_game_location.json"));
-                        }
-                    }
-                }
-                
-                #[cfg(target_os = "windows")]
-                {
-                    // On Windows, resources are typically in the same directory as the exe
-                    resource_paths.push(exe_dir.join("save_game_location.json"));
-                    resource_paths.push(exe_dir.join("resources").join("save_game_location.json"));
-                }
-                
-                #[cfg(target_os = "linux")]
-                {
-                    // On Linux, could be in various locations
-                    resource_paths.push(exe_dir.join("save_game_location.json"));
-                    resource_paths.push(exe_dir.join("resources").join("save_game_location.json"));
-                    // AppImage structure
-                    resource_paths.push(exe_dir.join("../lib/rogame").join("save_game_location.json"));
-                }
-                
-                // Common fallback paths
-                resource_paths.push(exe_dir.join("_up_").join("Resources").join("save_game_location.json"));
-
-                println!("Searching for save_game_location.json in production mode:");
-                for path in &resource_paths {
-                    println!("  Checking: {:?} - exists: {}", path, path.exists());
-                    if path.exists() {
-                        println!("  Found config at: {:?}", path);
-                        return path.clone();
-                    }
-                }
-                
-                println!("WARNING: Could not find save_game_location.json in any expected location!");
-            }
-        }
-
-        // Fallback - this will likely fail but provides error info
-        println!("FALLBACK: Using relative path save_game_location.json");
-        PathBuf::from("save_game_location.json")
-    }
-}
-
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct GameInfo {
     id: String,
     title: String,
@@ -77,47 +36,37 @@ pub struct GameInfo {
     category: String,
     is_favorite: bool,
     save_locations: Vec<SaveLocation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    install_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wine_prefix: Option<String>,
+    // Steam installation the game was found in (Steam games and shortcuts only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    steam_root: Option<String>,
+    // Install state and last update time from the Steam app manifest
+    #[serde(skip_serializing_if = "Option::is_none")]
+    install_state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_updated: Option<String>,
+    // Game database entry the game was matched to; detections of the same entry on
+    // several stores are merged into one game
+    #[serde(skip_serializing_if = "Option::is_none")]
+    database_id: Option<String>,
+    // The game's id on each store it was detected in
+    store_ids: Vec<StoreId>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SaveLocation {
     path: String,
     file_count: i32,
     total_size: String,
     last_modified: String,
-}
-
-#[derive(Debug, Deserialize)]
-struct GameEntry {
-    steam_id: String,
-    name: String,
-    save_locations: SaveLocations,
-    save_pattern: Vec<String>,
-}
-
-#[derive(Debug, Deserialize)]
-struct SaveLocations {
-    #[serde(default)]
-    macos: String,
-    #[serde(default)]
-    windows: String,
-    #[serde(default)]
-    linux: String,
-}
-
-// Helper function to get platform-specific save location
-fn get_platform_save_location(locations: &SaveLocations) -> &str {
-    #[cfg(target_os = "macos")]
-    return &locations.macos;
-
-    #[cfg(target_os = "windows")]
-    return &locations.windows;
-
-    #[cfg(target_os = "linux")]
-    return &locations.linux;
-
-    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
-    return "";
+    // Steam account owning the location (userdata/<id>/...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    steam_account_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    steam_persona_name: Option<String>,
 }
 
 // Common game installation directories
@@ -132,6 +81,8 @@ const STEAM_ROOT_PATHS: &[&str] = &[
     "~/Library/Application Support/Steam", // macOS
     "~/.steam/steam",                      // Linux
     "~/.local/share/Steam",                // Linux alternative
+    "~/.var/app/com.valvesoftware.Steam/.local/share/Steam", // Linux (Flatpak)
+    "~/snap/steam/common/.local/share/Steam", // Linux (Snap)
     "C:\\Program Files (x86)\\Steam",      // Windows
     "C:\\Program Files\\Steam",            // Windows alternative
 ];
@@ -152,7 +103,12 @@ const _STEAM_USERDATA_PATHS: &[&str] = &[
     "C:\\Program Files (x86)\\Steam\\userdata",     // Windows
 ];
 
-fn expand_tilde(path: &str) -> PathBuf {
+// Id of a game that has no Steam id or store id to go by, derived from its name
+pub(crate) fn game_id_from_name(name: &str) -> String {
+    name.to_lowercase().replace(" ", "_").replace(":", "")
+}
+
+pub(crate) fn expand_tilde(path: &str) -> PathBuf {
     // Handle tilde expansion for Unix-like paths
     if path.starts_with("~/") {
         if let Some(home) = dirs::home_dir() {
@@ -176,91 +132,77 @@ fn expand_tilde(path: &str) -> PathBuf {
 #[derive(Debug)]
 struct SteamLibrary {
     path: PathBuf,
+    // Steam installation whose libraryfolders.vdf lists this library
+    steam_root: PathBuf,
     installed_app_ids: Vec<String>,
 }
 
-// Parse Steam's libraryfolders.vdf to find all Steam library locations and installed games
-fn get_steam_libraries_with_games() -> Vec<SteamLibrary> {
-    let mut libraries = Vec::new();
+// Every Steam installation on the machine, deduplicated by canonical path
+// (~/.steam/steam is usually a symlink to ~/.local/share/Steam)
+pub(crate) fn steam_roots() -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = Vec::new();
 
-    // Try to find and parse libraryfolders.vdf
     for steam_root in STEAM_ROOT_PATHS {
         let steam_path = expand_tilde(steam_root);
+        if !steam_path.join("steamapps").is_dir() && !steam_path.join("userdata").is_dir() {
+            continue;
+        }
+
+        let canonical = steam_path.canonicalize().unwrap_or(steam_path);
+        if !roots.contains(&canonical) {
+            roots.push(canonical);
+        }
+    }
+
+    roots
+}
+
+// Parse every Steam root's libraryfolders.vdf to find all library locations and installed games
+fn get_steam_libraries_with_games() -> Vec<SteamLibrary> {
+    let mut libraries: Vec<SteamLibrary> = Vec::new();
+
+    for steam_path in steam_roots() {
         let vdf_path = steam_path.join("steamapps").join("libraryfolders.vdf");
 
         println!("Checking for Steam library config at: {:?}", vdf_path);
 
-        if vdf_path.exists() {
-            println!("Found libraryfolders.vdf at: {:?}", vdf_path);
-            if let Ok(content) = fs::read_to_string(&vdf_path) {
-                let mut current_library_path: Option<PathBuf> = None;
-                let mut current_apps: Vec<String> = Vec::new();
-                let mut in_apps_section = false;
-
-                for line in content.lines() {
-                    let trimmed = line.trim();
+        if !vdf_path.exists() {
+            continue;
+        }
 
-                    // Check if we're entering an "apps" section
-                    if trimmed == "\"apps\"" {
-                        in_apps_section = true;
+        println!("Found libraryfolders.vdf at: {:?}", vdf_path);
+        match fs::read_to_string(&vdf_path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| vdf::parse_library_folders(&content))
+        {
+            Ok(folders) => {
+                for folder in folders {
+                    let library_path = folder.path.join("steamapps").join("common");
+                    let Ok(library_path) = library_path.canonicalize() else {
                         continue;
-                    }
+                    };
 
-                    // Check if we're exiting an apps section
-                    if in_apps_section && trimmed == "}" {
-                        in_apps_section = false;
-
-                        // Save the library if we have a path and apps
-                        if let Some(path) = current_library_path.take() {
-                            let library_path = path.join("steamapps").join("common");
-                            if library_path.exists() {
-                                libraries.push(SteamLibrary {
-                                    path: library_path,
-                                    installed_app_ids: current_apps.clone(),
-                                });
-                            }
-                        }
-                        current_apps.clear();
+                    // The same library can be listed by more than one root
+                    if libraries.iter().any(|lib| lib.path == library_path) {
                         continue;
                     }
 
-                    // Parse path
-                    if trimmed.contains("\"path\"") && trimmed.contains("\"") {
-                        if let Some(path_start) = trimmed.rfind("\"") {
-                            if let Some(value_start) = trimmed[..path_start].rfind("\"") {
-                                let path_str = &trimmed[value_start + 1..path_start];
-                                let cleaned_path = path_str.replace("\\\\", "\\");
-                                current_library_path = Some(PathBuf::from(cleaned_path));
-                            }
-                        }
-                    }
-
-                    // Parse app IDs inside apps section
-                    if in_apps_section && trimmed.contains("\"") {
-                        // Extract app ID (first quoted value)
-                        if let Some(first_quote) = trimmed.find("\"") {
-                            if let Some(second_quote) = trimmed[first_quote + 1..].find("\"") {
-                                let app_id =
-                                    &trimmed[first_quote + 1..first_quote + 1 + second_quote];
-                                if !app_id.is_empty() && app_id.chars().all(|c| c.is_digit(10)) {
-                                    current_apps.push(app_id.to_string());
-                                }
-                            }
-                        }
-                    }
+                    libraries.push(SteamLibrary {
+                        path: library_path,
+                        steam_root: steam_path.clone(),
+                        installed_app_ids: folder.app_ids(),
+                    });
                 }
-            } else {
-                println!("Failed to read libraryfolders.vdf");
             }
-            break; // Found one valid libraryfolders.vdf, no need to check others
+            Err(e) => println!("Failed to read libraryfolders.vdf: {}", e),
         }
     }
 
     println!("Total Steam libraries found: {}", libraries.len());
     for lib in &libraries {
         println!(
-            "Library: {:?}, Games: {:?}",
-            lib.path, lib.installed_app_ids
+            "Library: {:?} (root {:?}), Games: {:?}",
+            lib.path, lib.steam_root, lib.installed_app_ids
         );
     }
 
@@ -270,6 +212,10 @@ fn get_steam_libraries_with_games() -> Vec<SteamLibrary> {
             let expanded = expand_tilde(path);
             if expanded.exists() {
                 libraries.push(SteamLibrary {
+                    steam_root: expanded
+                        .parent()
+                        .and_then(Path::parent)
+                        .map_or_else(|| expanded.clone(), Path::to_path_buf),
                     path: expanded,
                     installed_app_ids: Vec::new(), // Will scan all folders
                 });
@@ -280,48 +226,257 @@ fn get_steam_libraries_with_games() -> Vec<SteamLibrary> {
     libraries
 }
 
+// The libraries of every Steam installation. Parsing libraryfolders.vdf is done once per
+// scan or request and the result passed to whatever needs it.
+#[derive(Debug, Default)]
+pub(crate) struct SteamLibraries {
+    libraries: Vec<SteamLibrary>,
+}
+
+impl SteamLibraries {
+    pub fn load() -> Self {
+        Self {
+            libraries: get_steam_libraries_with_games(),
+        }
+    }
+
+    // Steam root that has an app installed in one of its libraries
+    pub fn root_for_app(&self, app_id: &str) -> Option<&Path> {
+        self.libraries
+            .iter()
+            .find(|library| library.installed_app_ids.iter().any(|id| id == app_id))
+            .map(|library| library.steam_root.as_path())
+    }
+
+    // Root directories of every Steam library (the folders containing `steamapps`)
+    #[cfg(target_os = "linux")]
+    pub fn library_roots(&self) -> Vec<PathBuf> {
+        self.libraries
+            .iter()
+            .filter_map(|library| {
+                library
+                    .path
+                    .parent()
+                    .and_then(Path::parent)
+                    .map(Path::to_path_buf)
+            })
+            .collect()
+    }
+}
+
+// Point a configured save location that lives inside a Steam installation
+// (e.g. "~/.local/share/Steam/userdata/*/<appid>/remote") at the given Steam root instead
+fn rebase_on_steam_root(save_location: &str, steam_root: &Path) -> Option<PathBuf> {
+    let normalized = save_location.replace('\\', "/");
+    STEAM_ROOT_PATHS.iter().find_map(|root| {
+        let root = root.replace('\\', "/");
+        let rest = normalized.strip_prefix(&root)?;
+        if !rest.is_empty() && !rest.starts_with('/') {
+            return None;
+        }
+        Some(steam_root.join(rest.trim_start_matches('/')))
+    })
+}
+
+// Expand a configured save location, resolving Steam paths against the game's Steam root.
+// Without a known root, the first installation where the location exists is used.
+pub(crate) fn resolve_save_location_path(
+    save_location: &str,
+    steam_root: Option<&Path>,
+) -> PathBuf {
+    let roots = match steam_root {
+        Some(root) => vec![root.to_path_buf()],
+        None => steam_roots(),
+    };
+
+    let candidates: Vec<PathBuf> = roots
+        .iter()
+        .filter_map(|root| rebase_on_steam_root(save_location, root))
+        .collect();
+
+    candidates
+        .iter()
+        .find(|path| {
+            let path = path.to_string_lossy();
+            if save_location.contains('*') {
+                glob(&path)
+                    .map(|mut paths| paths.any(|p| p.is_ok()))
+                    .unwrap_or(false)
+            } else {
+                Path::new(path.as_ref()).exists()
+            }
+        })
+        .or(candidates.first().filter(|_| steam_root.is_some()))
+        .cloned()
+        .unwrap_or_else(|| expand_tilde(save_location))
+}
+
+// The parts of a Steam app manifest used by the scan, as kept in the scan cache
+#[derive(Debug, Serialize, Deserialize)]
+struct SteamManifestInfo {
+    name: String,
+    install_dir: String,
+    size_on_disk: Option<u64>,
+    install_state: String,
+    last_updated: Option<u64>,
+    last_played: Option<u64>,
+}
+
 // Get game info from Steam app manifest
-fn get_steam_game_info(library_path: &PathBuf, app_id: &str) -> Option<(String, String)> {
+fn get_steam_game_info(
+    cache: &ScanCache,
+    library_path: &Path,
+    app_id: &str,
+) -> Option<SteamManifestInfo> {
     // Go back to steamapps folder from common folder
-    if let Some(steamapps_path) = library_path.parent() {
-        let manifest_path = steamapps_path.join(format!("appmanifest_{}.acf", app_id));
-
-        if manifest_path.exists() {
-            if let Ok(content) = fs::read_to_string(&manifest_path) {
-                let mut name = None;
-                let mut installdir = None;
-                
-                // Simple parser for ACF format
-                for line in content.lines() {
-                    let trimmed = line.trim();
-                    
-                    // Parse name field
-                    if trimmed.contains("\"name\"") && trimmed.contains("\"") {
-                        if let Some(name_start) = trimmed.rfind("\"") {
-                            if let Some(value_start) = trimmed[..name_start].rfind("\"") {
-                                name = Some(trimmed[value_start + 1..name_start].to_string());
-                            }
+    let steamapps_path = library_path.parent()?;
+    let manifest_path = steamapps_path.join(format!("appmanifest_{}.acf", app_id));
+
+    cache.cached("steam_manifest", &manifest_path, || {
+        let content = fs::read_to_string(&manifest_path).ok()?;
+        match vdf::parse_app_manifest(&content) {
+            Ok(manifest) if !manifest.name.is_empty() && !manifest.install_dir.is_empty() => {
+                Some(SteamManifestInfo {
+                    install_state: manifest.install_state().to_string(),
+                    name: manifest.name,
+                    install_dir: manifest.install_dir,
+                    size_on_disk: manifest.size_on_disk,
+                    last_updated: manifest.last_updated,
+                    last_played: manifest.last_played,
+                })
+            }
+            Ok(_) => None,
+            Err(e) => {
+                println!("Failed to parse {:?}: {}", manifest_path, e);
+                None
+            }
+        }
+    })
+}
+
+// Collect non-Steam shortcuts from every userdata/<id>/config/shortcuts.vdf, tagged with
+// the Steam root they were found in
+fn get_steam_shortcuts() -> Vec<(PathBuf, vdf::Shortcut)> {
+    let mut shortcuts: Vec<(PathBuf, vdf::Shortcut)> = Vec::new();
+
+    for steam_root in steam_roots() {
+        let userdata_path = steam_root.join("userdata");
+        let Ok(users) = fs::read_dir(&userdata_path) else {
+            continue;
+        };
+
+        for user in users.filter_map(|e| e.ok()) {
+            let shortcuts_path = user.path().join("config").join("shortcuts.vdf");
+            let Ok(data) = fs::read(&shortcuts_path) else {
+                continue;
+            };
+
+            match vdf::parse_shortcuts(&data) {
+                Ok(found) => {
+                    println!("Found {} shortcuts in {:?}", found.len(), shortcuts_path);
+                    for shortcut in found {
+                        // The same shortcut shows up once per account that added it
+                        if !shortcuts.iter().any(|(_, s)| s.app_id == shortcut.app_id) {
+                            shortcuts.push((steam_root.clone(), shortcut));
                         }
                     }
-                    
-                    // Parse installdir field
-                    if trimmed.contains("\"installdir\"") && trimmed.contains("\"") {
-                        if let Some(dir_start) = trimmed.rfind("\"") {
-                            if let Some(value_start) = trimmed[..dir_start].rfind("\"") {
-                                installdir = Some(trimmed[value_start + 1..dir_start].to_string());
-                            }
+                }
+                Err(e) => println!("Failed to parse {:?}: {}", shortcuts_path, e),
+            }
+        }
+    }
+
+    shortcuts
+}
+
+// A Steam account known on this machine, identified by its userdata/<id> folder name
+#[derive(Debug, Clone, Serialize)]
+pub struct SteamAccount {
+    pub account_id: String,
+    pub steam_id64: Option<String>,
+    pub account_name: String,
+    pub persona_name: String,
+    pub most_recent: bool,
+    pub steam_root: String,
+}
+
+// Accounts from every Steam root's config/loginusers.vdf, plus userdata folders of accounts
+// that are no longer listed there
+pub(crate) fn steam_accounts() -> Vec<SteamAccount> {
+    let mut accounts: Vec<SteamAccount> = Vec::new();
+
+    for steam_root in steam_roots() {
+        let login_users_path = steam_root.join("config").join("loginusers.vdf");
+        if let Ok(content) = fs::read_to_string(&login_users_path) {
+            match vdf::parse_login_users(&content) {
+                Ok(users) => {
+                    for user in users {
+                        let account_id = user.account_id.to_string();
+                        if accounts.iter().any(|a| a.account_id == account_id) {
+                            continue;
                         }
+                        accounts.push(SteamAccount {
+                            account_id,
+                            steam_id64: Some(user.steam_id64.to_string()),
+                            account_name: user.account_name,
+                            persona_name: user.persona_name,
+                            most_recent: user.most_recent,
+                            steam_root: steam_root.to_string_lossy().into_owned(),
+                        });
                     }
                 }
-                
-                // Return both if we found them
-                if let (Some(n), Some(d)) = (name, installdir) {
-                    return Some((n, d));
-                }
+                Err(e) => println!("Failed to parse {:?}: {}", login_users_path, e),
             }
         }
+
+        let Ok(entries) = fs::read_dir(steam_root.join("userdata")) else {
+            continue;
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let account_id = entry.file_name().to_string_lossy().into_owned();
+            if !entry.path().is_dir()
+                || !account_id.chars().all(|c| c.is_ascii_digit())
+                || accounts.iter().any(|a| a.account_id == account_id)
+            {
+                continue;
+            }
+            accounts.push(SteamAccount {
+                account_id,
+                steam_id64: None,
+                account_name: String::new(),
+                persona_name: String::new(),
+                most_recent: false,
+                steam_root: steam_root.to_string_lossy().into_owned(),
+            });
+        }
     }
-    None
+
+    accounts
+}
+
+// Steam account id of a path inside userdata/<id>/...
+pub(crate) fn steam_account_in_path(path: &Path) -> Option<String> {
+    let mut components = path
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned());
+
+    components.find(|component| component == "userdata")?;
+    components
+        .next()
+        .filter(|account_id| account_id.chars().all(|c| c.is_ascii_digit()))
+}
+
+// Narrow a save location that globs across every account (userdata/*/...) to one account
+pub(crate) fn save_location_for_account(save_location: &str, account_id: &str) -> Option<String> {
+    ["userdata/*/", "userdata\\*\\"].iter().find_map(|pattern| {
+        let start = save_location.find(pattern)? + "userdata/".len();
+        Some(format!(
+            "{}{}{}",
+            &save_location[..start],
+            account_id,
+            &save_location[start + 1..]
+        ))
+    })
 }
 
 fn get_directory_size(path: &PathBuf) -> u64 {
@@ -333,6 +488,15 @@ fn get_directory_size(path: &PathBuf) -> u64 {
         .fold(0, |acc, m| acc + m.len())
 }
 
+// Format a Unix timestamp (seconds) in local time, as used for last played dates
+fn format_timestamp(timestamp: i64) -> Option<String> {
+    chrono::DateTime::from_timestamp(timestamp, 0).map(|t| {
+        t.with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+    })
+}
+
 fn format_size(size: u64) -> String {
     const KB: u64 = 1024;
     const MB: u64 = KB * 1024;
@@ -350,57 +514,59 @@ fn format_size(size: u64) -> String {
 }
 
 fn scan_save_locations(
+    context: &ScanContext,
     steam_id: &str,
-    game_config: &HashMap<String, GameEntry>,
+    resolve_context: &ResolveContext,
 ) -> Vec<SaveLocation> {
+    let ScanContext { game_config, .. } = context;
     let mut save_locations = Vec::new();
 
     // Look up game by steam_id
     if let Some(game_info) = game_config.get(steam_id) {
-        // Get platform-specific save location
-        let save_location = get_platform_save_location(&game_info.save_locations);
-
-        if !save_location.is_empty() {
-            // Store the original pattern (before expansion) for proper wildcard handling
-            let original_pattern = save_location.to_string();
-            let expanded_path = expand_tilde(save_location);
+        for location in save_paths::resolve_locations(game_info, resolve_context) {
+            let found_before = save_locations.len();
+            let expanded_path = expand_tilde(&location.path);
 
             // Check if path contains wildcard *
-            if save_location.contains("*") {
+            if location.has_wildcard() {
                 // Use glob to find all matching paths
-                let glob_path = expanded_path.to_string_lossy().into_owned();
-                if let Ok(paths) = glob(&glob_path) {
-                    let mut found_any = false;
-                    for path_result in paths {
-                        if let Ok(path) = path_result {
-                            if path.is_dir() {
-                                scan_location_with_patterns(
-                                    &path,
-                                    &game_info.save_pattern,
-                                    &mut save_locations,
-                                );
-                                found_any = true;
-                            }
-                        }
-                    }
-                    // If we found locations, update the first one with the original pattern
-                    if found_any && !save_locations.is_empty() {
-                        save_locations[0].path = original_pattern.clone();
-                    }
-                }
-            } else {
-                // No wildcard, check path directly
-                if expanded_path.exists() {
-                    scan_location_with_patterns(
-                        &expanded_path,
-                        &game_info.save_pattern,
-                        &mut save_locations,
-                    );
-                    // Store the original pattern for consistency
-                    if !save_locations.is_empty() {
-                        save_locations[0].path = original_pattern;
+                if let Ok(paths) = glob(&expanded_path.to_string_lossy()) {
+                    for path in paths.filter_map(Result::ok).filter(|path| path.is_dir()) {
+                        scan_location_with_patterns(context, &path, &location, &mut save_locations);
                     }
                 }
+            } else if expanded_path.exists() {
+                scan_location_with_patterns(
+                    context,
+                    &expanded_path,
+                    &location,
+                    &mut save_locations,
+                );
+            }
+
+            // The first match of a location keeps the configured path (before wildcard
+            // expansion) so backups cover every match
+            if let Some(first) = save_locations.get_mut(found_before) {
+                first.path = location.path.clone();
+            }
+        }
+
+        // Windows-only games keep their saves inside the Proton prefix
+        #[cfg(target_os = "linux")]
+        if save_locations.is_empty() {
+            let roots = context.steam_libraries.library_roots();
+            for location in
+                save_paths::resolve_windows_locations(game_info, resolve_context, |path| {
+                    proton::resolve_save_location(steam_id, path, &roots)
+                })
+            {
+                println!("Found Proton save location: {:?}", location.path);
+                scan_location_with_patterns(
+                    context,
+                    Path::new(&location.path),
+                    &location,
+                    &mut save_locations,
+                );
             }
         }
     }
@@ -408,21 +574,37 @@ fn scan_save_locations(
     save_locations
 }
 
-// Helper function to scan a location with given patterns
+// Helper function to scan a location with its include/exclude globs. This takes a single stat
+// of each save file, so it isn't cached: any stamp proving the location unchanged would cost
+// as much as reading it again.
 fn scan_location_with_patterns(
-    path: &PathBuf,
-    patterns: &[String],
+    context: &ScanContext,
+    path: &Path,
+    location: &ResolvedLocation,
     save_locations: &mut Vec<SaveLocation>,
 ) {
+    if let Some(location) = read_save_location(path, location, &context.steam_accounts) {
+        save_locations.push(location);
+    }
+}
+
+fn read_save_location(
+    path: &Path,
+    location: &ResolvedLocation,
+    steam_accounts: &[SteamAccount],
+) -> Option<SaveLocation> {
     let mut total_size = 0u64;
     let mut file_count = 0;
     let mut latest_modified = std::time::SystemTime::UNIX_EPOCH;
 
     // Check if pattern is "*" - meaning backup whole directory
-    if patterns.len() == 1 && patterns[0] == "*" {
+    if location.includes_everything() {
         // Scan all files in the directory
         if let Ok(entries) = fs::read_dir(path) {
             for entry in entries.filter_map(Result::ok) {
+                if location.is_excluded(Path::new(&entry.file_name())) {
+                    continue;
+                }
                 if let Ok(metadata) = entry.metadata() {
                     if metadata.is_file() {
                         total_size += metadata.len();
@@ -438,10 +620,16 @@ fn scan_location_with_patterns(
         }
     } else {
         // Use glob patterns for specific file types
-        for pattern in patterns {
+        for pattern in &location.include {
             let glob_pattern = path.join(pattern).to_string_lossy().into_owned();
             if let Ok(entries) = glob(&glob_pattern) {
                 for entry in entries.filter_map(Result::ok) {
+                    if entry
+                        .strip_prefix(path)
+                        .is_ok_and(|relative| location.is_excluded(relative))
+                    {
+                        continue;
+                    }
                     if let Ok(metadata) = entry.metadata() {
                         total_size += metadata.len();
                         file_count += 1;
@@ -456,7 +644,7 @@ fn scan_location_with_patterns(
         }
     }
 
-    if file_count > 0 || (patterns.len() == 1 && patterns[0] == "*") {
+    if file_count > 0 || location.includes_everything() {
         let last_modified = if latest_modified > std::time::SystemTime::UNIX_EPOCH {
             chrono::DateTime::<chrono::Local>::from(latest_modified)
                 .format("%Y-%m-%d %H:%M:%S")
@@ -465,53 +653,354 @@ fn scan_location_with_patterns(
             "Unknown".to_string()
         };
 
-        save_locations.push(SaveLocation {
+        let steam_account_id = steam_account_in_path(path);
+        let steam_persona_name = steam_account_id.as_deref().and_then(|account_id| {
+            steam_accounts
+                .iter()
+                .find(|account| account.account_id == account_id)
+                .map(|account| account.persona_name.clone())
+                .filter(|name| !name.is_empty())
+        });
+
+        Some(SaveLocation {
             path: path.to_string_lossy().into_owned(),
             file_count,
             total_size: format_size(total_size),
             last_modified,
-        });
+            steam_account_id,
+            steam_persona_name,
+        })
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScanReport {
+    scan_id: String,
+    cancelled: bool,
+    games: HashMap<String, GameInfo>,
+    #[serde(flatten)]
+    changes: ScanChanges,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ScanProgressEvent {
+    scan_id: String,
+    completed: usize,
+    total: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ScanGameFoundEvent {
+    scan_id: String,
+    game: GameInfo,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ScanFinishedEvent {
+    scan_id: String,
+    cancelled: bool,
+    game_count: usize,
+    #[serde(flatten)]
+    changes: ScanChanges,
+}
+
+// Upper bound on scan worker threads
+const MAX_SCAN_WORKERS: usize = 8;
+
+// Cancellation flags of the scans currently running, by scan id
+static ACTIVE_SCANS: Lazy<Mutex<HashMap<String, Arc<AtomicBool>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[tauri::command]
+pub async fn scan_games(
+    app: AppHandle,
+    force_full_rescan: Option<bool>,
+    scan_id: Option<String>,
+    include_orphaned: Option<bool>,
+) -> Result<HashMap<String, GameInfo>, String> {
+    let report = scan_games_report(app, force_full_rescan, scan_id, include_orphaned).await?;
+    if report.cancelled {
+        return Err("Scan cancelled".to_string());
+    }
+    Ok(report.games)
+}
+
+// Scan all launchers, reusing cached results for unchanged paths unless a full rescan is
+// forced, and report which games were added, removed or changed since the previous scan.
+// Progress is emitted as `scan-progress`, `scan-game-found` and `scan-finished` events.
+// With `include_orphaned`, every game database entry without an installed game is also
+// checked for save data and reported with status "not_installed".
+#[tauri::command]
+pub async fn scan_games_report(
+    app: AppHandle,
+    force_full_rescan: Option<bool>,
+    scan_id: Option<String>,
+    include_orphaned: Option<bool>,
+) -> Result<ScanReport, String> {
+    let include_orphaned = include_orphaned.unwrap_or(false);
+    let scan_id = scan_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let cancelled = Arc::new(AtomicBool::new(false));
+    ACTIVE_SCANS
+        .lock()
+        .map_err(|_| "Failed to acquire scan lock".to_string())?
+        .insert(scan_id.clone(), cancelled.clone());
+
+    let cache = Arc::new(ScanCache::load(force_full_rescan.unwrap_or(false)).await);
+    let result = tokio::task::spawn_blocking({
+        let app = app.clone();
+        let cache = cache.clone();
+        let cancelled = cancelled.clone();
+        let scan_id = scan_id.clone();
+        move || {
+            scan_all_games(&cache, &cancelled, include_orphaned, |completed, total, game| {
+                if let Some(game) = game {
+                    let _ = app.emit(
+                        "scan-game-found",
+                        ScanGameFoundEvent {
+                            scan_id: scan_id.clone(),
+                            game: game.clone(),
+                        },
+                    );
+                }
+                let _ = app.emit(
+                    "scan-progress",
+                    ScanProgressEvent {
+                        scan_id: scan_id.clone(),
+                        completed,
+                        total,
+                    },
+                );
+            })
+        }
+    })
+    .await
+    .map_err(|e| format!("Scan task failed: {}", e));
+
+    if let Ok(mut scans) = ACTIVE_SCANS.lock() {
+        scans.remove(&scan_id);
     }
+    let games = result??;
+    let cancelled = cancelled.load(Ordering::SeqCst);
+
+    // A cancelled scan only saw part of the library, so neither the cache nor the
+    // results are stored
+    let changes = if cancelled {
+        println!("Scan {} cancelled", scan_id);
+        ScanChanges::default()
+    } else {
+        if let Some(cache) = Arc::into_inner(cache) {
+            if let Err(e) = cache.save().await {
+                println!("Failed to save scan cache: {}", e);
+            }
+        }
+
+        let current = games
+            .iter()
+            .map(|(game_id, game)| {
+                serde_json::to_string(game)
+                    .map(|data| (game_id.clone(), data))
+                    .map_err(|e| e.to_string())
+            })
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        scan_cache::record_scan_results(current)
+            .await
+            .unwrap_or_else(|e| {
+                println!("Failed to record scan results: {}", e);
+                ScanChanges::default()
+            })
+    };
+    println!(
+        "Scan finished: {} games, {} added, {} removed, {} changed",
+        games.len(),
+        changes.added.len(),
+        changes.removed.len(),
+        changes.changed.len()
+    );
+
+    let _ = app.emit(
+        "scan-finished",
+        ScanFinishedEvent {
+            scan_id: scan_id.clone(),
+            cancelled,
+            game_count: games.len(),
+            changes: ScanChanges {
+                added: changes.added.clone(),
+                removed: changes.removed.clone(),
+                changed: changes.changed.clone(),
+            },
+        },
+    );
+
+    Ok(ScanReport {
+        scan_id,
+        cancelled,
+        games,
+        changes,
+    })
 }
 
+// Request cancellation of a running scan. Returns false if no scan has that id.
 #[tauri::command]
-pub async fn scan_games() -> Result<HashMap<String, GameInfo>, String> {
+pub async fn cancel_scan(scan_id: String) -> Result<bool, String> {
+    let scans = ACTIVE_SCANS
+        .lock()
+        .map_err(|_| "Failed to acquire scan lock".to_string())?;
+    match scans.get(&scan_id) {
+        Some(cancelled) => {
+            println!("Cancelling scan {}", scan_id);
+            cancelled.store(true, Ordering::SeqCst);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+// Shared, read-only state of one scan run
+struct ScanContext<'a> {
+    cache: &'a ScanCache,
+    game_config: Arc<HashMap<String, GameEntry>>,
+    steam_libraries: SteamLibraries,
+    // Accounts from loginusers.vdf, for naming the owners of per-account save locations
+    steam_accounts: Vec<SteamAccount>,
+}
+
+// A unit of scan work; jobs run concurrently on the scan worker pool
+enum ScanJob {
+    // Folder in a Steam library without libraryfolders.vdf app data
+    SteamDirectory {
+        steam_root: PathBuf,
+        game_name: String,
+        game_path: PathBuf,
+    },
+    SteamApp {
+        steam_root: PathBuf,
+        library_path: PathBuf,
+        app_id: String,
+    },
+    SteamShortcut {
+        steam_root: PathBuf,
+        shortcut: vdf::Shortcut,
+    },
+    EpicManifest(PathBuf),
+    Heroic(heroic::HeroicGame),
+    Lutris(lutris::LutrisGame),
+    // Game database entry with no installed game, checked for leftover saves
+    Orphaned(String),
+}
+
+fn scan_all_games<F>(
+    cache: &ScanCache,
+    cancelled: &AtomicBool,
+    include_orphaned: bool,
+    mut on_progress: F,
+) -> Result<HashMap<String, GameInfo>, String>
+where
+    F: FnMut(usize, usize, Option<&GameInfo>),
+{
     println!("Starting game scan...");
     println!("Operating System: {}", std::env::consts::OS);
 
+    // Load save game configuration
+    let game_config = game_database::load()?;
+
+    let context = ScanContext {
+        cache,
+        game_config,
+        steam_libraries: SteamLibraries::load(),
+        steam_accounts: steam_accounts(),
+    };
+    let jobs = collect_scan_jobs(&context.steam_libraries);
+    println!("Scanning {} candidate games", jobs.len());
+
+    let launcher_total = jobs.len();
     let mut games = HashMap::new();
+    for game in run_scan_jobs(&context, jobs, cancelled, &mut on_progress)
+        .into_iter()
+        .flatten()
+    {
+        merge_detection(&mut games, game);
+    }
 
-    // Load save game configuration
-    let config_path = get_save_config_path();
-    println!("Loading save game config from: {:?}", config_path);
-
-    let game_config: HashMap<String, GameEntry> = match fs::read_to_string(&config_path) {
-        Ok(content) => serde_json::from_str(&content).map_err(|e| {
-            println!("Error parsing config: {}", e);
-            e.to_string()
-        })?,
-        Err(e) => {
-            println!("Error reading config file: {}", e);
-            println!("Using embedded config as fallback...");
-            
-            // Use embedded config as fallback for production builds
-            const EMBEDDED_CONFIG: &str = include_str!("save_game_location.json");
-            match serde_json::from_str(EMBEDDED_CONFIG) {
-                Ok(config) => {
-                    println!("Successfully loaded embedded config");
-                    config
-                },
-                Err(parse_err) => {
-                    println!("Error parsing embedded config: {}", parse_err);
-                    // Return empty config as last resort
-                    HashMap::new()
-                }
-            }
+    if include_orphaned {
+        // Entries matched by an installed game are skipped
+        let installed: HashSet<&str> = games
+            .values()
+            .filter_map(|game| game.database_id.as_deref())
+            .collect();
+        let mut orphaned_ids: Vec<String> = context
+            .game_config
+            .keys()
+            .filter(|id| !installed.contains(id.as_str()))
+            .cloned()
+            .collect();
+        orphaned_ids.sort();
+        println!("Checking {} uninstalled games for saves", orphaned_ids.len());
+
+        // Progress continues from the launcher jobs, with the total grown by the orphan jobs
+        let total = launcher_total + orphaned_ids.len();
+        let jobs = orphaned_ids.into_iter().map(ScanJob::Orphaned).collect();
+        let orphaned = run_scan_jobs(&context, jobs, cancelled, |completed, _, game| {
+            on_progress(launcher_total + completed, total, game)
+        });
+        for game in orphaned.into_iter().flatten() {
+            merge_detection(&mut games, game);
         }
+    }
+
+    Ok(games)
+}
+
+// Add a detection to the games found so far. Games matched to a database entry are keyed by
+// it, so detections of the same entry on several stores are merged into one game; otherwise
+// later detections win on duplicate ids, as launchers are scanned in order.
+fn merge_detection(games: &mut HashMap<String, GameInfo>, game: GameInfo) {
+    let existing = game.database_id.as_ref().and_then(|database_id| {
+        games
+            .values_mut()
+            .find(|other| other.database_id.as_ref() == Some(database_id))
+    });
+    let Some(existing) = existing else {
+        games.insert(game.id.clone(), game);
+        return;
     };
 
+    println!(
+        "Merging {} ({}) into {} ({})",
+        game.id, game.platform, existing.id, existing.platform
+    );
+    for store_id in game.store_ids {
+        if !existing.store_ids.contains(&store_id) {
+            existing.store_ids.push(store_id);
+        }
+    }
+    for location in game.save_locations {
+        if !existing
+            .save_locations
+            .iter()
+            .any(|other| other.path == location.path)
+        {
+            existing.save_locations.push(location);
+        }
+    }
+    existing.save_count = existing
+        .save_locations
+        .iter()
+        .map(|loc| loc.file_count)
+        .sum();
+    if existing.save_count > 0 && existing.status == "no_saves" {
+        existing.status = "has_saves".to_string();
+    }
+}
+
+// Enumerate the games to scan across every launcher. Only cheap discovery happens here;
+// manifests, install directories and save locations are read by the jobs.
+fn collect_scan_jobs(steam_libraries: &SteamLibraries) -> Vec<ScanJob> {
+    let mut jobs = Vec::new();
+
     // Scan Steam games from all library folders using VDF data
-    let steam_libraries = get_steam_libraries_with_games();
+    let steam_libraries = &steam_libraries.libraries;
     println!("Found {} Steam library folders", steam_libraries.len());
 
     for library in steam_libraries {
@@ -520,64 +1009,15 @@ pub async fn scan_games() -> Result<HashMap<String, GameInfo>, String> {
         if library.installed_app_ids.is_empty() {
             // Fallback: scan all directories if no app IDs found
             println!("No app IDs found, scanning all directories in library");
-            if library.path.exists() {
-                if let Ok(entries) = fs::read_dir(&library.path) {
-                    for entry in entries.filter_map(|e| e.ok()) {
-                        if entry.path().is_dir() {
-                            if let Ok(game_name) = entry.file_name().into_string() {
-                                let game_path = entry.path();
-                                println!("Found game directory: {} at {:?}", game_name, game_path);
-                                let size = get_directory_size(&game_path);
-                                // Try to find game by name in config
-                                let game_entry = game_config
-                                    .values()
-                                    .find(|entry| entry.name.eq_ignore_ascii_case(&game_name));
-
-                                let (game_id, save_locations, category) = if let Some(entry) =
-                                    game_entry
-                                {
-                                    let locations =
-                                        scan_save_locations(&entry.steam_id, &game_config);
-                                    (entry.steam_id.clone(), locations, "Action".to_string())
-                                } else {
-                                    // Game not in config, use empty save locations
-                                    let game_id =
-                                        game_name.to_lowercase().replace(" ", "_").replace(":", "");
-                                    (game_id, Vec::new(), "Unknown".to_string())
-                                };
-
-                                let save_count =
-                                    save_locations.iter().map(|loc| loc.file_count).sum();
-                                // let cover_image = format!("https://cdn.cloudflare.steamstatic.com/steam/apps/{}/hero_capsule.jpg",
-                                let cover_image = format!("https://steamcdn-a.akamaihd.net/steam/apps/{}/library_600x900_2x.jpg",   
-                                    if game_id.chars().all(|c| c.is_numeric()) { &game_id } else { "1245620" });
-
-                                games.insert(
-                                    game_id.clone(),
-                                    GameInfo {
-                                        id: game_id,
-                                        title: game_name,
-                                        cover_image,
-                                        platform: "Steam".to_string(),
-                                        last_played: save_locations
-                                            .first()
-                                            .map_or("Never".to_string(), |loc| {
-                                                loc.last_modified.clone()
-                                            }),
-                                        save_count,
-                                        size: format_size(size),
-                                        status: if save_count > 0 {
-                                            "has_saves"
-                                        } else {
-                                            "no_saves"
-                                        }
-                                        .to_string(),
-                                        category,
-                                        is_favorite: false,
-                                        save_locations,
-                                    },
-                                );
-                            }
+            if let Ok(entries) = fs::read_dir(&library.path) {
+                for entry in entries.filter_map(|e| e.ok()) {
+                    if entry.path().is_dir() {
+                        if let Ok(game_name) = entry.file_name().into_string() {
+                            jobs.push(ScanJob::SteamDirectory {
+                                steam_root: library.steam_root.clone(),
+                                game_name,
+                                game_path: entry.path(),
+                            });
                         }
                     }
                 }
@@ -589,164 +1029,683 @@ pub async fn scan_games() -> Result<HashMap<String, GameInfo>, String> {
                 library.installed_app_ids.len()
             );
             for app_id in &library.installed_app_ids {
-                // Try to get game info from manifest
-                if let Some((game_name, install_dir)) = get_steam_game_info(&library.path, app_id) {
-                    let game_path = library.path.join(&install_dir);
-
-                    if game_path.exists() {
-                        println!(
-                            "Found game: {} (App ID: {}) at {:?}",
-                            game_name, app_id, game_path
-                        );
-                        let size = get_directory_size(&game_path);
-                        // Check if game exists in config with this steam_id
-                        let save_locations = scan_save_locations(app_id, &game_config);
-                        let save_count = save_locations.iter().map(|loc| loc.file_count).sum();
-
-                        // Get game info from config if available
-                        let category = if game_config.contains_key(app_id) {
-                            "Action".to_string() // Default category for now
-                        } else {
-                            "Unknown".to_string()
-                        };
-
-                        // let cover_image = format!(
-                        //     "https://cdn.cloudflare.steamstatic.com/steam/apps/{}/hero_capsule.jpg",
-                        //     app_id
-                        // );
-                        let cover_image = format!(
-                            "https://steamcdn-a.akamaihd.net/steam/apps/{}/library_600x900_2x.jpg",
-                            app_id
-                        );
-
-                        games.insert(
-                            app_id.clone(),
-                            GameInfo {
-                                id: app_id.clone(),
-                                title: game_name,
-                                cover_image,
-                                platform: "Steam".to_string(),
-                                last_played: save_locations
-                                    .first()
-                                    .map_or("Never".to_string(), |loc| loc.last_modified.clone()),
-                                save_count,
-                                size: format_size(size),
-                                status: if save_count > 0 {
-                                    "has_saves"
-                                } else {
-                                    "no_saves"
-                                }
-                                .to_string(),
-                                category,
-                                is_favorite: false,
-                                save_locations,
-                            },
-                        );
-                    } else {
-                        println!(
-                            "Game directory not found for: {} (App ID: {})",
-                            game_name, app_id
-                        );
-                    }
-                } else {
-                    println!("Could not get game name for App ID: {}", app_id);
-                }
+                jobs.push(ScanJob::SteamApp {
+                    steam_root: library.steam_root.clone(),
+                    library_path: library.path.clone(),
+                    app_id: app_id.clone(),
+                });
             }
         }
     }
 
-    // Scan Epic games
+    // Non-Steam games added to Steam as shortcuts
+    for (steam_root, shortcut) in get_steam_shortcuts() {
+        jobs.push(ScanJob::SteamShortcut {
+            steam_root,
+            shortcut,
+        });
+    }
+
+    // Epic games
     for epic_path in EPIC_PATHS {
-        let path = expand_tilde(epic_path);
-        if path.exists() {
-            if let Ok(entries) = fs::read_dir(&path) {
-                for entry in entries.filter_map(|e| e.ok()) {
-                    if entry.path().is_file()
-                        && entry.path().extension().map_or(false, |ext| ext == "item")
-                    {
-                        if let Ok(content) = fs::read_to_string(entry.path()) {
-                            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) {
-                                if let Some(display_name) = json["DisplayName"].as_str() {
-                                    if let Some(install_location) = json["InstallLocation"].as_str()
-                                    {
-                                        let game_path = PathBuf::from(install_location);
-                                        let size = get_directory_size(&game_path);
-                                        // Get Epic App Name (ID) from manifest
-                                        let epic_app_name = json["AppName"].as_str().unwrap_or("");
-
-                                        // Try to find game in config by name
-                                        let game_entry = game_config.values().find(|entry| {
-                                            entry.name.eq_ignore_ascii_case(display_name)
-                                        });
-
-                                        let (game_id, save_locations, category) =
-                                            if let Some(entry) = game_entry {
-                                                let locations = scan_save_locations(
-                                                    &entry.steam_id,
-                                                    &game_config,
-                                                );
-                                                (
-                                                    format!("epic_{}", entry.steam_id),
-                                                    locations,
-                                                    "Action".to_string(),
-                                                )
-                                            } else {
-                                                // Game not in config
-                                                let game_id = if !epic_app_name.is_empty() {
-                                                    format!("epic_{}", epic_app_name)
-                                                } else {
-                                                    format!(
-                                                        "epic_{}",
-                                                        display_name
-                                                            .to_lowercase()
-                                                            .replace(" ", "_")
-                                                            .replace(":", "")
-                                                    )
-                                                };
-                                                (game_id, Vec::new(), "Unknown".to_string())
-                                            };
-
-                                        let save_count =
-                                            save_locations.iter().map(|loc| loc.file_count).sum();
-                                        // let cover_image = "https://cdn.cloudflare.steamstatic.com/steam/apps/1551360/hero_capsule.jpg".to_string();
-                                        let cover_image = format!("https://steamcdn-a.akamaihd.net/steam/apps/{}/library_600x900_2x.jpg", game_id);
-
-                                        games.insert(
-                                            game_id.clone(),
-                                            GameInfo {
-                                                id: game_id,
-                                                title: display_name.to_string(),
-                                                cover_image,
-                                                platform: "Epic Games".to_string(),
-                                                last_played: save_locations
-                                                    .first()
-                                                    .map_or("Never".to_string(), |loc| {
-                                                        loc.last_modified.clone()
-                                                    }),
-                                                save_count,
-                                                size: format_size(size),
-                                                status: if save_count > 0 {
-                                                    "has_saves"
-                                                } else {
-                                                    "no_saves"
-                                                }
-                                                .to_string(),
-                                                category,
-                                                is_favorite: false,
-                                                save_locations,
-                                            },
-                                        );
-                                    }
-                                }
-                            }
-                        }
-                    }
+        if let Ok(entries) = fs::read_dir(expand_tilde(epic_path)) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                if entry.path().is_file()
+                    && entry.path().extension().map_or(false, |ext| ext == "item")
+                {
+                    jobs.push(ScanJob::EpicManifest(entry.path()));
                 }
             }
         }
     }
 
-    Ok(games)
+    // Epic and GOG games installed through Heroic Games Launcher
+    jobs.extend(heroic::installed_games().into_iter().map(ScanJob::Heroic));
+
+    // Games installed through Lutris
+    jobs.extend(lutris::installed_games().into_iter().map(ScanJob::Lutris));
+
+    jobs
+}
+
+// Run the jobs on a bounded pool of worker threads. Results keep the job order; progress
+// is reported from the calling thread as jobs complete. Once `cancelled` is set, no new
+// jobs are started.
+fn run_scan_jobs<F>(
+    context: &ScanContext,
+    jobs: Vec<ScanJob>,
+    cancelled: &AtomicBool,
+    mut on_progress: F,
+) -> Vec<Option<GameInfo>>
+where
+    F: FnMut(usize, usize, Option<&GameInfo>),
+{
+    let total = jobs.len();
+    let workers = std::thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .clamp(1, MAX_SCAN_WORKERS)
+        .min(total.max(1));
+    let queue = Mutex::new(jobs.into_iter().enumerate());
+    let mut results: Vec<Option<GameInfo>> = (0..total).map(|_| None).collect();
+
+    std::thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel();
+
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let queue = &queue;
+            scope.spawn(move || loop {
+                if cancelled.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Some((index, job)) = queue.lock().ok().and_then(|mut jobs| jobs.next()) else {
+                    break;
+                };
+                if sender.send((index, scan_job(context, job))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        for (completed, (index, game)) in receiver.iter().enumerate() {
+            on_progress(completed + 1, total, game.as_ref());
+            results[index] = game;
+        }
+    });
+
+    results
+}
+
+fn scan_job(context: &ScanContext, job: ScanJob) -> Option<GameInfo> {
+    match job {
+        ScanJob::SteamDirectory {
+            steam_root,
+            game_name,
+            game_path,
+        } => Some(scan_steam_directory(context, &steam_root, game_name, game_path)),
+        ScanJob::SteamApp {
+            steam_root,
+            library_path,
+            app_id,
+        } => scan_steam_app(context, &steam_root, &library_path, &app_id),
+        ScanJob::SteamShortcut {
+            steam_root,
+            shortcut,
+        } => Some(scan_steam_shortcut(context, &steam_root, shortcut)),
+        ScanJob::EpicManifest(manifest_path) => scan_epic_manifest(context, &manifest_path),
+        ScanJob::Heroic(heroic_game) => Some(scan_heroic_game(context, heroic_game)),
+        ScanJob::Lutris(lutris_game) => Some(scan_lutris_game(context, lutris_game)),
+        ScanJob::Orphaned(steam_id) => scan_orphaned_game(context, &steam_id),
+    }
+}
+
+// Report a game database entry whose game isn't installed, if it left saves behind
+fn scan_orphaned_game(context: &ScanContext, steam_id: &str) -> Option<GameInfo> {
+    let ScanContext { game_config, .. } = context;
+    let entry = game_config.get(steam_id)?;
+
+    // Without an install, only locations outside the install directory can be checked
+    let save_locations = scan_save_locations(context, steam_id, &ResolveContext::default());
+    if save_locations.is_empty() {
+        return None;
+    }
+    println!("Found saves for uninstalled game: {} ({})", entry.name, steam_id);
+
+    let is_steam_app = steam_id.chars().all(|c| c.is_ascii_digit());
+    let cover_image = if is_steam_app {
+        format!(
+            "https://steamcdn-a.akamaihd.net/steam/apps/{}/library_600x900_2x.jpg",
+            steam_id
+        )
+    } else {
+        String::new()
+    };
+
+    Some(GameInfo {
+        id: steam_id.to_string(),
+        title: entry.name.clone(),
+        cover_image,
+        platform: if is_steam_app { "Steam" } else { "Unknown" }.to_string(),
+        last_played: save_locations
+            .first()
+            .map_or("Never".to_string(), |loc| loc.last_modified.clone()),
+        save_count: save_locations.iter().map(|loc| loc.file_count).sum(),
+        size: format_size(0),
+        status: "not_installed".to_string(),
+        category: "Action".to_string(),
+        is_favorite: false,
+        save_locations,
+        install_path: None,
+        wine_prefix: None,
+        steam_root: None,
+        install_state: None,
+        last_updated: None,
+        database_id: Some(steam_id.to_string()),
+        store_ids: Vec::new(),
+    })
+}
+
+fn scan_steam_directory(
+    context: &ScanContext,
+    steam_root: &Path,
+    game_name: String,
+    game_path: PathBuf,
+) -> GameInfo {
+    let ScanContext { game_config, .. } = context;
+    println!("Found game directory: {} at {:?}", game_name, game_path);
+    let size = get_directory_size(&game_path);
+    // Try to find game by name in config
+    let game_entry = game_config
+        .values()
+        .find(|entry| entry.name.eq_ignore_ascii_case(&game_name));
+
+    let (game_id, save_locations, category) = if let Some(entry) = game_entry {
+        let locations = scan_save_locations(
+            context,
+            &entry.steam_id,
+            &ResolveContext {
+                steam_root: Some(steam_root),
+                install_dir: Some(&game_path),
+                store: Some("steam"),
+                ..Default::default()
+            },
+        );
+        (entry.steam_id.clone(), locations, "Action".to_string())
+    } else {
+        // Game not in config, use empty save locations
+        let game_id = game_id_from_name(&game_name);
+        (game_id, Vec::new(), "Unknown".to_string())
+    };
+
+    let save_count = save_locations.iter().map(|loc| loc.file_count).sum();
+    // let cover_image = format!("https://cdn.cloudflare.steamstatic.com/steam/apps/{}/hero_capsule.jpg",
+    let cover_image = format!("https://steamcdn-a.akamaihd.net/steam/apps/{}/library_600x900_2x.jpg",   
+        if game_id.chars().all(|c| c.is_numeric()) { &game_id } else { "1245620" });
+
+    GameInfo {
+        id: game_id,
+        title: game_name,
+        cover_image,
+        platform: "Steam".to_string(),
+        last_played: save_locations
+            .first()
+            .map_or("Never".to_string(), |loc| loc.last_modified.clone()),
+        save_count,
+        size: format_size(size),
+        status: if save_count > 0 {
+            "has_saves"
+        } else {
+            "no_saves"
+        }
+        .to_string(),
+        category,
+        is_favorite: false,
+        save_locations,
+        install_path: Some(game_path.to_string_lossy().into_owned()),
+        wine_prefix: None,
+        steam_root: Some(steam_root.to_string_lossy().into_owned()),
+        install_state: None,
+        last_updated: None,
+        database_id: game_entry.map(|entry| entry.steam_id.clone()),
+        store_ids: game_entry
+            .map(|entry| vec![StoreId::new("steam", &entry.steam_id)])
+            .unwrap_or_default(),
+    }
+}
+
+fn scan_steam_app(
+    context: &ScanContext,
+    steam_root: &Path,
+    library_path: &Path,
+    app_id: &str,
+) -> Option<GameInfo> {
+    let ScanContext {
+        cache, game_config, ..
+    } = context;
+
+    // Try to get game info from manifest
+    let Some(manifest) = get_steam_game_info(cache, library_path, app_id) else {
+        println!("Could not get game name for App ID: {}", app_id);
+        return None;
+    };
+    let game_path = library_path.join(&manifest.install_dir);
+
+    if !game_path.exists() {
+        println!(
+            "Game directory not found for: {} (App ID: {})",
+            manifest.name, app_id
+        );
+        return None;
+    }
+
+    println!(
+        "Found game: {} (App ID: {}) at {:?}",
+        manifest.name, app_id, game_path
+    );
+    // Size comes from the manifest; only walk the install directory
+    // when Steam hasn't recorded it
+    let size = match manifest.size_on_disk {
+        Some(size) if size > 0 => size,
+        _ => get_directory_size(&game_path),
+    };
+    // Check if game exists in config with this steam_id
+    let save_locations = scan_save_locations(
+        context,
+        app_id,
+        &ResolveContext {
+            steam_root: Some(steam_root),
+            install_dir: Some(&game_path),
+            store: Some("steam"),
+            ..Default::default()
+        },
+    );
+    let save_count = save_locations.iter().map(|loc| loc.file_count).sum();
+
+    // Get game info from config if available
+    let category = if game_config.contains_key(app_id) {
+        "Action".to_string() // Default category for now
+    } else {
+        "Unknown".to_string()
+    };
+
+    // let cover_image = format!(
+    //     "https://cdn.cloudflare.steamstatic.com/steam/apps/{}/hero_capsule.jpg",
+    //     app_id
+    // );
+    let cover_image = format!(
+        "https://steamcdn-a.akamaihd.net/steam/apps/{}/library_600x900_2x.jpg",
+        app_id
+    );
+
+    Some(GameInfo {
+        id: app_id.to_string(),
+        title: manifest.name,
+        cover_image,
+        platform: "Steam".to_string(),
+        last_played: manifest
+            .last_played
+            .filter(|&t| t > 0)
+            .and_then(|t| format_timestamp(t as i64))
+            .or_else(|| save_locations.first().map(|loc| loc.last_modified.clone()))
+            .unwrap_or_else(|| "Never".to_string()),
+        save_count,
+        size: format_size(size),
+        status: if save_count > 0 {
+            "has_saves"
+        } else {
+            "no_saves"
+        }
+        .to_string(),
+        category,
+        is_favorite: false,
+        save_locations,
+        install_path: Some(game_path.to_string_lossy().into_owned()),
+        wine_prefix: None,
+        steam_root: Some(steam_root.to_string_lossy().into_owned()),
+        install_state: Some(manifest.install_state),
+        last_updated: manifest
+            .last_updated
+            .and_then(|t| format_timestamp(t as i64)),
+        database_id: game_config
+            .contains_key(app_id)
+            .then(|| app_id.to_string()),
+        store_ids: vec![StoreId::new("steam", app_id)],
+    })
+}
+
+fn scan_steam_shortcut(
+    context: &ScanContext,
+    steam_root: &Path,
+    shortcut: vdf::Shortcut,
+) -> GameInfo {
+    let ScanContext { game_config, .. } = context;
+    let app_id = shortcut.app_id.to_string();
+    let start_dir = PathBuf::from(shortcut.start_dir.trim_matches('"'));
+    println!(
+        "Found Steam shortcut: {} (App ID: {}) at {:?}",
+        shortcut.app_name, app_id, start_dir
+    );
+
+    // Config entries may target the shortcut's app id directly, otherwise match by name
+    let config_id = if game_config.contains_key(&app_id) {
+        Some(app_id.clone())
+    } else {
+        game_config
+            .values()
+            .find(|entry| entry.name.eq_ignore_ascii_case(&shortcut.app_name))
+            .map(|entry| entry.steam_id.clone())
+    };
+
+    let (save_locations, category) = match &config_id {
+        Some(id) => (
+            scan_save_locations(
+                context,
+                id,
+                &ResolveContext {
+                    steam_root: Some(steam_root),
+                    install_dir: Some(start_dir.as_path()).filter(|dir| dir.is_dir()),
+                    ..Default::default()
+                },
+            ),
+            "Action".to_string(),
+        ),
+        None => (Vec::new(), "Unknown".to_string()),
+    };
+    let save_count = save_locations.iter().map(|loc| loc.file_count).sum();
+    let size = if start_dir.is_dir() {
+        get_directory_size(&start_dir)
+    } else {
+        0
+    };
+
+    // Shortcut app ids are derived from the executable and name, so they stay stable
+    let store_ids = vec![StoreId::new("steam_shortcut", &app_id)];
+
+    GameInfo {
+        id: app_id,
+        title: shortcut.app_name,
+        cover_image: shortcut.icon,
+        platform: "Steam (Shortcut)".to_string(),
+        last_played: save_locations
+            .first()
+            .map(|loc| loc.last_modified.clone())
+            .or_else(|| {
+                shortcut
+                    .last_play_time
+                    .and_then(|t| format_timestamp(t as i64))
+            })
+            .unwrap_or_else(|| "Never".to_string()),
+        save_count,
+        size: format_size(size),
+        status: if save_count > 0 {
+            "has_saves"
+        } else {
+            "no_saves"
+        }
+        .to_string(),
+        category,
+        is_favorite: false,
+        save_locations,
+        // The start directory is often a system one such as /usr/bin, not the game's own
+        install_path: None,
+        wine_prefix: None,
+        steam_root: Some(steam_root.to_string_lossy().into_owned()),
+        install_state: None,
+        last_updated: None,
+        database_id: config_id,
+        store_ids,
+    }
+}
+
+fn scan_epic_manifest(context: &ScanContext, manifest_path: &Path) -> Option<GameInfo> {
+    let ScanContext { game_config, .. } = context;
+    let content = fs::read_to_string(manifest_path).ok()?;
+    let json = serde_json::from_str::<serde_json::Value>(&content).ok()?;
+    let display_name = json["DisplayName"].as_str()?;
+    let install_location = json["InstallLocation"].as_str()?;
+
+    let game_path = PathBuf::from(install_location);
+    let size = get_directory_size(&game_path);
+    // Get Epic App Name (ID) from manifest
+    let epic_app_name = json["AppName"].as_str().unwrap_or("");
+
+    // Try to find game in config by name
+    let game_entry = game_config
+        .values()
+        .find(|entry| entry.name.eq_ignore_ascii_case(display_name));
+
+    let (game_id, save_locations, category) = if let Some(entry) = game_entry {
+        let locations = scan_save_locations(
+            context,
+            &entry.steam_id,
+            &ResolveContext {
+                install_dir: Some(&game_path),
+                store: Some("epic"),
+                ..Default::default()
+            },
+        );
+        (entry.steam_id.clone(), locations, "Action".to_string())
+    } else {
+        // Game not in config
+        let game_id = if !epic_app_name.is_empty() {
+            format!("epic_{}", epic_app_name)
+        } else {
+            format!("epic_{}", game_id_from_name(display_name))
+        };
+        (game_id, Vec::new(), "Unknown".to_string())
+    };
+
+    let save_count = save_locations.iter().map(|loc| loc.file_count).sum();
+    // let cover_image = "https://cdn.cloudflare.steamstatic.com/steam/apps/1551360/hero_capsule.jpg".to_string();
+    let cover_image = format!("https://steamcdn-a.akamaihd.net/steam/apps/{}/library_600x900_2x.jpg", game_id);
+
+    Some(GameInfo {
+        id: game_id,
+        title: display_name.to_string(),
+        cover_image,
+        platform: "Epic Games".to_string(),
+        last_played: save_locations
+            .first()
+            .map_or("Never".to_string(), |loc| loc.last_modified.clone()),
+        save_count,
+        size: format_size(size),
+        status: if save_count > 0 {
+            "has_saves"
+        } else {
+            "no_saves"
+        }
+        .to_string(),
+        category,
+        is_favorite: false,
+        save_locations,
+        install_path: Some(install_location.to_string()),
+        wine_prefix: None,
+        steam_root: None,
+        install_state: None,
+        last_updated: None,
+        database_id: game_entry.map(|entry| entry.steam_id.clone()),
+        store_ids: if epic_app_name.is_empty() {
+            Vec::new()
+        } else {
+            vec![StoreId::new("epic", epic_app_name)]
+        },
+    })
+}
+
+fn scan_heroic_game(context: &ScanContext, heroic_game: heroic::HeroicGame) -> GameInfo {
+    let ScanContext { game_config, .. } = context;
+    println!(
+        "Found Heroic game: {} ({} {}) at {:?}",
+        heroic_game.title,
+        heroic_game.store.platform_name(),
+        heroic_game.app_name,
+        heroic_game.install_path
+    );
+
+    let game_entry = game_config
+        .values()
+        .find(|entry| entry.name.eq_ignore_ascii_case(&heroic_game.title));
+
+    let resolve_context = ResolveContext {
+        install_dir: Some(&heroic_game.install_path),
+        store: Some(heroic_game.store.id_prefix()),
+        ..Default::default()
+    };
+    let (game_id, mut save_locations, category) = match game_entry {
+        Some(entry) => (
+            entry.steam_id.clone(),
+            scan_save_locations(context, &entry.steam_id, &resolve_context),
+            "Action".to_string(),
+        ),
+        None => (
+            format!(
+                "{}_{}",
+                heroic_game.store.id_prefix(),
+                heroic_game.app_name
+            ),
+            Vec::new(),
+            "Unknown".to_string(),
+        ),
+    };
+
+    // Windows builds keep their saves inside the Heroic Wine/Proton prefix
+    #[cfg(target_os = "linux")]
+    if let (Some(entry), Some(prefix)) = (game_entry, &heroic_game.wine_prefix) {
+        if save_locations.is_empty() {
+            for location in save_paths::resolve_windows_locations(entry, &resolve_context, |path| {
+                proton::resolve_in_prefix(prefix, path)
+            }) {
+                println!("Found save location in Heroic prefix: {:?}", location.path);
+                scan_location_with_patterns(
+                    context,
+                    Path::new(&location.path),
+                    &location,
+                    &mut save_locations,
+                );
+            }
+        }
+    }
+
+    let save_count = save_locations.iter().map(|loc| loc.file_count).sum();
+    let size = heroic_game
+        .install_size
+        .unwrap_or_else(|| get_directory_size(&heroic_game.install_path));
+    let cover_image = format!(
+        "https://steamcdn-a.akamaihd.net/steam/apps/{}/library_600x900_2x.jpg",
+        game_entry.map_or("1245620", |entry| entry.steam_id.as_str())
+    );
+
+    GameInfo {
+        id: game_id,
+        title: heroic_game.title,
+        cover_image,
+        platform: heroic_game.store.platform_name().to_string(),
+        last_played: save_locations
+            .first()
+            .map_or("Never".to_string(), |loc| loc.last_modified.clone()),
+        save_count,
+        size: format_size(size),
+        status: if save_count > 0 {
+            "has_saves"
+        } else {
+            "no_saves"
+        }
+        .to_string(),
+        category,
+        is_favorite: false,
+        save_locations,
+        install_path: Some(heroic_game.install_path.to_string_lossy().into_owned()),
+        wine_prefix: heroic_game
+            .wine_prefix
+            .map(|prefix| prefix.to_string_lossy().into_owned()),
+        steam_root: None,
+        install_state: None,
+        last_updated: None,
+        database_id: game_entry.map(|entry| entry.steam_id.clone()),
+        store_ids: vec![StoreId::new(
+            heroic_game.store.id_prefix(),
+            &heroic_game.app_name,
+        )],
+    }
+}
+
+fn scan_lutris_game(context: &ScanContext, lutris_game: lutris::LutrisGame) -> GameInfo {
+    let ScanContext { game_config, .. } = context;
+    println!(
+        "Found Lutris game: {} (runner: {}) at {:?}",
+        lutris_game.name, lutris_game.runner, lutris_game.directory
+    );
+
+    // Match by Steam id first, then by name
+    let game_entry = lutris_game
+        .steam_id
+        .as_ref()
+        .and_then(|steam_id| game_config.get(steam_id))
+        .or_else(|| {
+            game_config
+                .values()
+                .find(|entry| entry.name.eq_ignore_ascii_case(&lutris_game.name))
+        });
+
+    let resolve_context = ResolveContext {
+        install_dir: lutris_game.directory.as_deref(),
+        ..Default::default()
+    };
+    let (game_id, mut save_locations, category) = match game_entry {
+        Some(entry) => (
+            entry.steam_id.clone(),
+            scan_save_locations(context, &entry.steam_id, &resolve_context),
+            "Action".to_string(),
+        ),
+        None => (
+            format!("lutris_{}", lutris_game.slug),
+            Vec::new(),
+            "Unknown".to_string(),
+        ),
+    };
+
+    // Wine games keep their saves inside the Lutris prefix
+    #[cfg(target_os = "linux")]
+    if let (Some(entry), Some(prefix)) = (game_entry, &lutris_game.wine_prefix) {
+        if save_locations.is_empty() {
+            for location in save_paths::resolve_windows_locations(entry, &resolve_context, |path| {
+                proton::resolve_in_prefix(prefix, path)
+            }) {
+                println!("Found save location in Lutris prefix: {:?}", location.path);
+                scan_location_with_patterns(
+                    context,
+                    Path::new(&location.path),
+                    &location,
+                    &mut save_locations,
+                );
+            }
+        }
+    }
+
+    let save_count = save_locations.iter().map(|loc| loc.file_count).sum();
+    let size = lutris_game.directory.as_ref().map_or(0, get_directory_size);
+    let cover_image = format!(
+        "https://steamcdn-a.akamaihd.net/steam/apps/{}/library_600x900_2x.jpg",
+        game_entry.map_or("1245620", |entry| entry.steam_id.as_str())
+    );
+    let last_played = save_locations
+        .first()
+        .map(|loc| loc.last_modified.clone())
+        .or_else(|| lutris_game.last_played.and_then(format_timestamp))
+        .unwrap_or_else(|| "Never".to_string());
+
+    GameInfo {
+        id: game_id,
+        title: lutris_game.name,
+        cover_image,
+        platform: "Lutris".to_string(),
+        last_played,
+        save_count,
+        size: format_size(size),
+        status: if save_count > 0 {
+            "has_saves"
+        } else {
+            "no_saves"
+        }
+        .to_string(),
+        category,
+        is_favorite: false,
+        save_locations,
+        install_path: lutris_game
+            .directory
+            .map(|dir| dir.to_string_lossy().into_owned()),
+        wine_prefix: lutris_game
+            .wine_prefix
+            .map(|prefix| prefix.to_string_lossy().into_owned()),
+        steam_root: None,
+        install_state: None,
+        last_updated: None,
+        database_id: game_entry.map(|entry| entry.steam_id.clone()),
+        store_ids: std::iter::once(StoreId::new("lutris", &lutris_game.slug))
+            .chain(lutris_game.steam_id.as_ref().map(|id| StoreId::new("steam", id)))
+            .collect(),
+    }
 }
 
 // Helper function to get backup directory path
@@ -767,16 +1726,7 @@ async fn _delete_save_file(game_id: String, save_id: String) -> Result<(), Strin
     let mut last_error = None;
 
     // 1. Delete from original save locations
-    let config_path = get_save_config_path();
-    let game_config: HashMap<String, GameEntry> = match fs::read_to_string(&config_path) {
-        Ok(content) => serde_json::from_str(&content).map_err(|e| e.to_string())?,
-        Err(e) => {
-            // Use embedded config as fallback
-            const EMBEDDED_CONFIG: &str = include_str!("save_game_location.json");
-            serde_json::from_str(EMBEDDED_CONFIG)
-                .map_err(|_| format!("Failed to read save game configuration: {}", e))?
-        }
-    };
+    let game_config = game_database::load()?;
 
     // Extract steam_id from game_id (remove epic_ prefix if present)
     let steam_id = if game_id.starts_with("epic_") {
@@ -789,10 +1739,9 @@ async fn _delete_save_file(game_id: String, save_id: String) -> Result<(), Strin
         .get(steam_id)
         .ok_or_else(|| format!("Game not found: {}", game_id))?;
 
-    // Get platform-specific save location
-    let save_location = get_platform_save_location(&game_info.save_locations);
-
-    if !save_location.is_empty() {
+    // Every location configured for this platform
+    for location in save_paths::resolve_locations(game_info, &ResolveContext::default()) {
+        let save_location = location.path.as_str();
         let expanded_path = expand_tilde(save_location);
 
         if save_location.contains("*") {
@@ -811,7 +1760,7 @@ async fn _delete_save_file(game_id: String, save_id: String) -> Result<(), Strin
                                 }
                             } else {
                                 // Try patterns
-                                for pattern in &game_info.save_pattern {
+                                for pattern in &location.include {
                                     let glob_pattern =
                                         path.join(pattern).to_string_lossy().into_owned();
                                     if let Ok(entries) = glob(&glob_pattern) {
@@ -842,7 +1791,7 @@ async fn _delete_save_file(game_id: String, save_id: String) -> Result<(), Strin
                         Err(e) => last_error = Some(e.to_string()),
                     }
                 } else {
-                    for pattern in &game_info.save_pattern {
+                    for pattern in &location.include {
                         let glob_pattern =
                             expanded_path.join(pattern).to_string_lossy().into_owned();
                         if let Ok(entries) = glob(&glob_pattern) {
@@ -890,16 +1839,7 @@ async fn _delete_save_file(game_id: String, save_id: String) -> Result<(), Strin
 // Removed unused function
 #[allow(dead_code)]
 fn _list_save_files(game_id: &str) -> Result<Vec<String>, String> {
-    let config_path = get_save_config_path();
-    let game_config: HashMap<String, GameEntry> = match fs::read_to_string(&config_path) {
-        Ok(content) => serde_json::from_str(&content).map_err(|e| e.to_string())?,
-        Err(e) => {
-            // Use embedded config as fallback
-            const EMBEDDED_CONFIG: &str = include_str!("save_game_location.json");
-            serde_json::from_str(EMBEDDED_CONFIG)
-                .map_err(|_| format!("Failed to read save game configuration: {}", e))?
-        }
-    };
+    let game_config = game_database::load()?;
 
     // Extract steam_id from game_id
     let steam_id = if game_id.starts_with("epic_") {
@@ -914,10 +1854,9 @@ fn _list_save_files(game_id: &str) -> Result<Vec<String>, String> {
 
     let mut save_files = Vec::new();
 
-    // Get platform-specific save location
-    let save_location = get_platform_save_location(&game_info.save_locations);
-
-    if !save_location.is_empty() {
+    // Every location configured for this platform
+    for location in save_paths::resolve_locations(game_info, &ResolveContext::default()) {
+        let save_location = location.path.as_str();
         let expanded_path = expand_tilde(save_location);
 
         if save_location.contains("*") {
@@ -927,7 +1866,7 @@ fn _list_save_files(game_id: &str) -> Result<Vec<String>, String> {
                 for path_result in paths {
                     if let Ok(path) = path_result {
                         if path.is_dir() {
-                            for pattern in &game_info.save_pattern {
+                            for pattern in &location.include {
                                 let glob_pattern =
                                     path.join(pattern).to_string_lossy().into_owned();
                                 if let Ok(entries) = glob(&glob_pattern) {
@@ -946,7 +1885,7 @@ fn _list_save_files(game_id: &str) -> Result<Vec<String>, String> {
         } else {
             // No wildcard
             if expanded_path.exists() {
-                for pattern in &game_info.save_pattern {
+                for pattern in &location.include {
                     let glob_pattern = expanded_path.join(pattern).to_string_lossy().into_owned();
                     if let Ok(entries) = glob(&glob_pattern) {
                         for entry in entries.filter_map(Result::ok) {
@@ -1040,3 +1979,168 @@ pub async fn delete_game_saves(game_id: String) -> Result<(), String> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steam_account_paths() {
+        let location = "~/.local/share/Steam/userdata/*/3489700/remote";
+
+        assert_eq!(
+            save_location_for_account(location, "22202").as_deref(),
+            Some("~/.local/share/Steam/userdata/22202/3489700/remote")
+        );
+        assert_eq!(
+            save_location_for_account("C:\\Steam\\userdata\\*\\730", "22202").as_deref(),
+            Some("C:\\Steam\\userdata\\22202\\730")
+        );
+        assert_eq!(save_location_for_account("~/AppData/Local/SB/*", "22202"), None);
+        assert_eq!(
+            steam_account_in_path(Path::new("/home/deck/.steam/steam/userdata/22202/730/remote"))
+                .as_deref(),
+            Some("22202")
+        );
+        assert_eq!(steam_account_in_path(Path::new("/home/deck/userdata/saves")), None);
+    }
+
+    #[test]
+    fn test_run_scan_jobs_progress_and_cancel() {
+        let cache = ScanCache::default();
+        let context = ScanContext {
+            cache: &cache,
+            game_config: Arc::new(HashMap::new()),
+            steam_libraries: SteamLibraries::default(),
+            steam_accounts: Vec::new(),
+        };
+        let jobs = || {
+            (0..20)
+                .map(|i| ScanJob::EpicManifest(PathBuf::from(format!("/missing/{}.item", i))))
+                .collect::<Vec<_>>()
+        };
+
+        let mut progress = Vec::new();
+        let not_cancelled = AtomicBool::new(false);
+        let results = run_scan_jobs(&context, jobs(), &not_cancelled, |completed, total, _| {
+            progress.push((completed, total))
+        });
+        assert_eq!(results.len(), 20);
+        assert_eq!(progress, (1..=20).map(|n| (n, 20)).collect::<Vec<_>>());
+
+        let mut calls = 0;
+        run_scan_jobs(&context, jobs(), &AtomicBool::new(true), |_, _, _| calls += 1);
+        assert_eq!(calls, 0);
+    }
+
+    fn detection(id: &str, database_id: Option<&str>, store_id: StoreId, path: &str) -> GameInfo {
+        GameInfo {
+            id: id.to_string(),
+            title: "Elden Ring".to_string(),
+            cover_image: String::new(),
+            platform: store_id.store.clone(),
+            last_played: "Never".to_string(),
+            save_count: 1,
+            size: format_size(0),
+            status: "has_saves".to_string(),
+            category: "Action".to_string(),
+            is_favorite: false,
+            save_locations: vec![SaveLocation {
+                path: path.to_string(),
+                file_count: 1,
+                total_size: format_size(0),
+                last_modified: "Never".to_string(),
+                steam_account_id: None,
+                steam_persona_name: None,
+            }],
+            install_path: None,
+            wine_prefix: None,
+            steam_root: None,
+            install_state: None,
+            last_updated: None,
+            database_id: database_id.map(str::to_string),
+            store_ids: vec![store_id],
+        }
+    }
+
+    #[test]
+    fn test_merge_detection() {
+        let mut games = HashMap::new();
+        let steam = StoreId::new("steam", "1245620");
+        let epic = StoreId::new("epic", "a1b2c3");
+        let gog = StoreId::new("gog", "1");
+        for game in [
+            detection("1245620", Some("1245620"), steam.clone(), "~/a"),
+            detection("1245620", Some("1245620"), epic.clone(), "~/a"),
+            detection("1245620", Some("1245620"), gog.clone(), "~/b"),
+            detection("epic_other", None, StoreId::new("epic", "x"), "~/c"),
+        ] {
+            merge_detection(&mut games, game);
+        }
+
+        assert_eq!(games.len(), 2);
+        let game = &games["1245620"];
+        assert_eq!(game.store_ids, vec![steam, epic, gog]);
+        assert_eq!(game.save_locations.len(), 2);
+        assert_eq!(game.save_count, 2);
+    }
+
+    #[test]
+    fn test_scan_orphaned_game() {
+        let dir = std::env::temp_dir().join(format!("rogame_orphaned_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("slot1.sav"), "save").unwrap();
+        let location = dir.to_string_lossy();
+
+        let game_config: HashMap<String, GameEntry> = serde_json::from_value(serde_json::json!({
+            "999999": {
+                "steam_id": "999999",
+                "name": "Uninstalled Game",
+                "save_locations": { "windows": location, "macos": location, "linux": location }
+            },
+            "888888": {
+                "steam_id": "888888",
+                "name": "Never Played",
+                "save_locations": { "windows": "", "macos": "", "linux": "" }
+            }
+        }))
+        .unwrap();
+        let cache = ScanCache::default();
+        let context = ScanContext {
+            cache: &cache,
+            game_config: Arc::new(game_config),
+            steam_libraries: SteamLibraries::default(),
+            steam_accounts: Vec::new(),
+        };
+
+        let game = scan_job(&context, ScanJob::Orphaned("999999".to_string())).unwrap();
+        assert_eq!(game.status, "not_installed");
+        assert_eq!(game.title, "Uninstalled Game");
+        assert_eq!(game.save_count, 1);
+        assert_eq!(game.install_path, None);
+
+        assert!(scan_job(&context, ScanJob::Orphaned("888888".to_string())).is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_rebase_on_steam_root() {
+        let flatpak = Path::new("/home/deck/.var/app/com.valvesoftware.Steam/.local/share/Steam");
+
+        assert_eq!(
+            rebase_on_steam_root("~/.local/share/Steam/userdata/*/3489700/remote", flatpak),
+            Some(flatpak.join("userdata/*/3489700/remote"))
+        );
+        assert_eq!(
+            rebase_on_steam_root("~/.steam/steam/userdata/*/730/local", flatpak),
+            Some(flatpak.join("userdata/*/730/local"))
+        );
+        assert_eq!(
+            rebase_on_steam_root("C:\\Program Files (x86)\\Steam\\userdata\\*\\730", flatpak),
+            Some(flatpak.join("userdata/*/730"))
+        );
+        assert_eq!(rebase_on_steam_root("~/.local/share/SteamGame/saves", flatpak), None);
+        assert_eq!(rebase_on_steam_root("~/AppData/Local/SB/Saved", flatpak), None);
+    }
+}
@@ -0,0 +1,268 @@
+use crate::game_scanner::expand_tilde;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Games installed through Heroic Games Launcher (Legendary for Epic, gogdl for GOG)
+
+// Heroic config directories: native install and Flatpak
+const HEROIC_CONFIG_PATHS: &[&str] = &[
+    "~/.config/heroic",                                     // Linux
+    "~/.var/app/com.heroicgameslauncher.hgl/config/heroic", // Linux (Flatpak)
+    "~/Library/Application Support/heroic",                 // macOS
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HeroicStore {
+    Legendary,
+    Gog,
+}
+
+impl HeroicStore {
+    pub fn platform_name(&self) -> &'static str {
+        match self {
+            HeroicStore::Legendary => "Epic Games",
+            HeroicStore::Gog => "GOG",
+        }
+    }
+
+    pub fn id_prefix(&self) -> &'static str {
+        match self {
+            HeroicStore::Legendary => "epic",
+            HeroicStore::Gog => "gog",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeroicGame {
+    pub store: HeroicStore,
+    pub app_name: String,
+    pub title: String,
+    pub install_path: PathBuf,
+    // "windows", "linux" or "osx" build of the game
+    pub platform: String,
+    pub install_size: Option<u64>,
+    pub wine_prefix: Option<PathBuf>,
+}
+
+// Legendary reports sizes as bytes, gogdl as a human readable string ("12.5 GiB")
+fn parse_size(value: &Value) -> Option<u64> {
+    if let Some(size) = value.as_u64() {
+        return Some(size);
+    }
+
+    let text = value.as_str()?.trim();
+    let (number, unit) = text.split_at(text.find(|c: char| c.is_alphabetic())?);
+    let number: f64 = number.trim().parse().ok()?;
+    let multiplier = match unit.trim().to_ascii_lowercase().as_str() {
+        "b" => 1u64,
+        "kb" | "kib" => 1 << 10,
+        "mb" | "mib" => 1 << 20,
+        "gb" | "gib" => 1 << 30,
+        "tb" | "tib" => 1 << 40,
+        _ => return None,
+    };
+    Some((number * multiplier as f64) as u64)
+}
+
+// Parse legendaryConfig/legendary/installed.json (object keyed by app name)
+pub fn parse_legendary_installed(content: &str) -> Result<Vec<HeroicGame>, String> {
+    let json: Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    let games = json
+        .as_object()
+        .ok_or_else(|| "installed.json is not an object".to_string())?;
+
+    Ok(games
+        .iter()
+        .filter(|(_, game)| !game["is_dlc"].as_bool().unwrap_or(false))
+        .filter_map(|(app_name, game)| {
+            Some(HeroicGame {
+                store: HeroicStore::Legendary,
+                app_name: game["app_name"].as_str().unwrap_or(app_name).to_string(),
+                title: game["title"].as_str().unwrap_or(app_name).to_string(),
+                install_path: PathBuf::from(game["install_path"].as_str()?),
+                platform: game["platform"]
+                    .as_str()
+                    .unwrap_or("Windows")
+                    .to_ascii_lowercase(),
+                install_size: parse_size(&game["install_size"]),
+                wine_prefix: None,
+            })
+        })
+        .collect())
+}
+
+// Parse gog_store/installed.json. Titles live in gog_store/library.json, so it is passed
+// along when available; otherwise the install folder name is used.
+pub fn parse_gog_installed(
+    content: &str,
+    library: Option<&str>,
+) -> Result<Vec<HeroicGame>, String> {
+    let json: Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    let installed = json["installed"]
+        .as_array()
+        .ok_or_else(|| "installed.json has no \"installed\" list".to_string())?;
+
+    let library: Value = library
+        .and_then(|content| serde_json::from_str(content).ok())
+        .unwrap_or(Value::Null);
+    let title_for = |app_name: &str| {
+        library["games"].as_array().and_then(|games| {
+            games
+                .iter()
+                .find(|game| game["app_name"].as_str() == Some(app_name))
+                .and_then(|game| game["title"].as_str())
+                .map(str::to_string)
+        })
+    };
+
+    Ok(installed
+        .iter()
+        .filter(|game| !game["is_dlc"].as_bool().unwrap_or(false))
+        .filter_map(|game| {
+            let app_name = game["appName"].as_str()?.to_string();
+            let install_path = PathBuf::from(game["install_path"].as_str()?);
+            let title = title_for(&app_name).unwrap_or_else(|| {
+                install_path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| app_name.clone())
+            });
+
+            Some(HeroicGame {
+                store: HeroicStore::Gog,
+                app_name,
+                title,
+                install_path,
+                platform: game["platform"]
+                    .as_str()
+                    .unwrap_or("windows")
+                    .to_ascii_lowercase(),
+                install_size: parse_size(&game["install_size"]),
+                wine_prefix: None,
+            })
+        })
+        .collect())
+}
+
+// Read the Wine prefix from GamesConfig/<appName>.json, falling back to Heroic's default
+// location. Proton prefixes keep the actual Windows tree in a `pfx` subdirectory.
+fn find_wine_prefix(config_root: &Path, game: &HeroicGame) -> Option<PathBuf> {
+    if game.platform != "windows" {
+        return None;
+    }
+
+    let configured = fs::read_to_string(
+        config_root
+            .join("GamesConfig")
+            .join(format!("{}.json", game.app_name)),
+    )
+    .ok()
+    .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+    .and_then(|json| {
+        json[&game.app_name]["winePrefix"]
+            .as_str()
+            .map(expand_tilde)
+    });
+
+    let prefix = configured
+        .unwrap_or_else(|| expand_tilde("~/Games/Heroic/Prefixes/default").join(&game.title));
+
+    [prefix.join("pfx"), prefix]
+        .into_iter()
+        .find(|candidate| candidate.join("drive_c").is_dir())
+}
+
+// Installed games from every Heroic config directory on the machine
+pub fn installed_games() -> Vec<HeroicGame> {
+    let mut games: Vec<HeroicGame> = Vec::new();
+
+    for config_path in HEROIC_CONFIG_PATHS {
+        let config_root = expand_tilde(config_path);
+        if !config_root.is_dir() {
+            continue;
+        }
+        println!("Checking Heroic config at: {:?}", config_root);
+
+        let legendary_path = config_root
+            .join("legendaryConfig")
+            .join("legendary")
+            .join("installed.json");
+        let legendary = fs::read_to_string(&legendary_path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| parse_legendary_installed(&content));
+
+        let gog_path = config_root.join("gog_store").join("installed.json");
+        let gog_library =
+            fs::read_to_string(config_root.join("gog_store").join("library.json")).ok();
+        let gog = fs::read_to_string(&gog_path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| parse_gog_installed(&content, gog_library.as_deref()));
+
+        for (path, result) in [(legendary_path, legendary), (gog_path, gog)] {
+            match result {
+                Ok(found) => {
+                    for mut game in found {
+                        if games
+                            .iter()
+                            .any(|g| g.store == game.store && g.app_name == game.app_name)
+                        {
+                            continue;
+                        }
+                        game.wine_prefix = find_wine_prefix(&config_root, &game);
+                        games.push(game);
+                    }
+                }
+                Err(e) if path.exists() => println!("Failed to read {:?}: {}", path, e),
+                Err(_) => {}
+            }
+        }
+    }
+
+    games
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_legendary_installed() {
+        let content = r#"{
+            "Fortnite": {"app_name": "Fortnite", "title": "Fortnite", "install_path": "/games/Fortnite", "platform": "Windows", "install_size": 1234, "is_dlc": false},
+            "SomeDlc": {"app_name": "SomeDlc", "title": "DLC", "install_path": "/games/Fortnite", "is_dlc": true},
+            "Broken": {"title": "No path"}
+        }"#;
+        let games = parse_legendary_installed(content).unwrap();
+
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].store, HeroicStore::Legendary);
+        assert_eq!(games[0].title, "Fortnite");
+        assert_eq!(games[0].install_path, PathBuf::from("/games/Fortnite"));
+        assert_eq!(games[0].platform, "windows");
+        assert_eq!(games[0].install_size, Some(1234));
+    }
+
+    #[test]
+    fn test_parse_gog_installed() {
+        let content = r#"{"installed": [
+            {"appName": "1207658924", "install_path": "/games/Witcher 3", "platform": "windows", "install_size": "35.5 GiB", "is_dlc": false},
+            {"appName": "1423049311", "install_path": "/games/Cyberpunk 2077", "platform": "linux", "install_size": "512 MB"}
+        ]}"#;
+        let library =
+            r#"{"games": [{"app_name": "1207658924", "title": "The Witcher 3: Wild Hunt"}]}"#;
+        let games = parse_gog_installed(content, Some(library)).unwrap();
+
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].title, "The Witcher 3: Wild Hunt");
+        assert_eq!(
+            games[0].install_size,
+            Some((35.5 * (1u64 << 30) as f64) as u64)
+        );
+        assert_eq!(games[1].title, "Cyberpunk 2077");
+        assert_eq!(games[1].platform, "linux");
+        assert_eq!(games[1].install_size, Some(512 << 20));
+        assert!(parse_gog_installed("{}", None).is_err());
+    }
+}
@@ -2,11 +2,26 @@
 use tauri::{Emitter, Manager};
 use tauri_plugin_deep_link::DeepLinkExt;
 
+mod backup_scheduler;
+mod backup_store;
 mod cloud_tokens;
 mod db;
+mod game_database;
+mod game_identity;
 mod game_scanner;
+mod heroic;
+mod ludusavi;
+mod lutris;
+mod process_monitor;
+mod retention;
+#[cfg(target_os = "linux")]
+mod proton;
 mod save_manager;
+mod save_paths;
+mod save_watcher;
+mod scan_cache;
 mod security;
+mod vdf;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -25,20 +40,41 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .invoke_handler(tauri::generate_handler![
             game_scanner::scan_games,
+            game_scanner::scan_games_report,
+            game_scanner::cancel_scan,
+            game_database::get_game_database,
+            game_database::save_game_database_entry,
+            game_database::delete_game_database_entry,
+            game_database::reset_game_database_entry,
+            ludusavi::import_ludusavi_manifest,
+            game_identity::get_game_store_ids,
+            game_identity::link_game_store_id,
+            game_identity::unlink_game_store_id,
             game_scanner::delete_game_saves,
             save_manager::backup_save,
             save_manager::restore_save,
             save_manager::list_saves,
             save_manager::get_all_save_files,
             save_manager::delete_save,
+            save_manager::collect_backup_garbage,
+            save_manager::verify_backup,
+            save_manager::verify_all_backups,
+            save_manager::prune_backups,
+            save_manager::set_save_pinned,
+            save_manager::update_save_metadata,
             save_manager::save_backup_settings,
             save_manager::load_backup_settings,
+            backup_scheduler::get_backup_schedule,
             save_manager::get_all_games,
             save_manager::get_game_by_id,
             save_manager::add_game,
             save_manager::update_game,
             save_manager::delete_game,
             save_manager::toggle_favorite,
+            save_manager::update_game_save_rules,
+            save_manager::update_game_backup_interval,
+            save_manager::set_game_watch_saves,
+            save_manager::update_game_retention_policy,
             save_manager::add_game_to_library,
             save_manager::add_game_manually,
             save_manager::read_file_as_bytes,
@@ -52,6 +88,7 @@ pub fn run() {
             save_manager::get_community_saves,
             save_manager::list_directory_files,
             save_manager::restore_community_save,
+            save_manager::get_steam_accounts,
             cloud_tokens::save_cloud_token,
             cloud_tokens::get_cloud_token,
             cloud_tokens::delete_cloud_token
@@ -87,6 +124,18 @@ pub fn run() {
 
             // macOS automatically registers schemes from Info.plist
 
+            // Link games added before game database ids were stored to their entries
+            tauri::async_runtime::spawn(game_identity::migrate_legacy_game_ids());
+
+            // Back up games in the background according to the backup settings
+            backup_scheduler::start(app.handle().clone());
+
+            // Back up games with save watching enabled whenever they write their saves
+            save_watcher::start(app.handle().clone());
+
+            // Record play sessions and back up games when they exit
+            process_monitor::start(app.handle().clone());
+
             Ok(())
         })
         .run(tauri::generate_context!())
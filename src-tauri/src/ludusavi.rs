@@ -0,0 +1,428 @@
+use crate::game_database::{self, GameDatabase};
+use crate::game_scanner::{expand_tilde, game_id_from_name};
+use crate::save_paths::{GameEntry, LocationSpec, SaveLocations};
+use crate::scan_cache::modified_time;
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+// Save game entries translated from a local copy of the community Ludusavi manifest
+// (manifest.yaml). Ludusavi lists the save files of each game with its own placeholders and
+// `when` conditions on the OS and store; registry entries are ignored.
+
+// Manifest imported through the app, in the rogame config dir
+const IMPORTED_MANIFEST: &str = "ludusavi_manifest.yaml";
+
+// Ludusavi's own copy, for installs outside the platform config dir
+const LUDUSAVI_MANIFEST_PATHS: &[&str] =
+    &["~/.var/app/com.github.mtkennerly.ludusavi/config/ludusavi/manifest.yaml"];
+
+// Parsed manifest with the path and mtime it was read from
+type CachedManifest = (PathBuf, i64, Arc<GameDatabase>);
+
+static MANIFEST_CACHE: Lazy<Mutex<Option<CachedManifest>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Default, Deserialize)]
+struct ManifestGame {
+    #[serde(default)]
+    files: Option<BTreeMap<String, Option<ManifestFile>>>,
+    #[serde(default)]
+    steam: Option<ManifestSteam>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ManifestFile {
+    #[serde(default)]
+    when: Option<Vec<ManifestCondition>>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+struct ManifestCondition {
+    os: Option<String>,
+    store: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestSteam {
+    id: Option<u64>,
+}
+
+// Ludusavi placeholders with a fixed rogame equivalent
+const PLACEHOLDERS: &[(&str, &str)] = &[
+    ("<root>/<game>", "<installDir>"),
+    ("<base>", "<installDir>"),
+    ("<osUserName>", "*"),
+    ("<winLocalAppDataLow>", "~/AppData/LocalLow"),
+    ("<winLocalAppData>", "~/AppData/Local"),
+    ("<winDocuments>", "~/Documents"),
+    ("<winProgramData>", "C:/ProgramData"),
+    ("<winDir>", "C:/Windows"),
+];
+
+// Translate a Ludusavi path for a store into a rogame location path
+fn translate_path(path: &str, store: Option<&str>, steam_id: Option<&str>) -> Option<String> {
+    let steam = store == Some("steam");
+    let mut translated = path.trim().replace('\\', "/");
+
+    for (placeholder, replacement) in PLACEHOLDERS {
+        translated = translated.replace(placeholder, replacement);
+    }
+    translated = translated.replace("<storeUserId>", if steam { "<steamUserId>" } else { "*" });
+    if translated.contains("<storeGameId>") {
+        translated = translated.replace("<storeGameId>", steam_id.filter(|_| steam)?);
+    }
+    // The store root is only known for Steam
+    if translated.contains("<root>") {
+        if !steam {
+            return None;
+        }
+        translated = translated.replace("<root>", "<steamRoot>");
+    }
+
+    // Anything left (<game>, <winPublic>, registry keys, ...) can't be resolved
+    let supported = [
+        "<home>",
+        "<xdgData>",
+        "<xdgConfig>",
+        "<steamRoot>",
+        "<steamUserId>",
+        "<installDir>",
+        "<winAppData>",
+    ];
+    let mut rest = translated.clone();
+    for placeholder in supported {
+        rest = rest.replace(placeholder, "");
+    }
+    (!rest.contains('<') && !translated.is_empty()).then_some(translated)
+}
+
+// OSes a path applies to when its conditions don't name one
+fn implied_oses(path: &str) -> &'static [&'static str] {
+    if path.contains("<win") || path.get(1..3) == Some(":/") {
+        &["windows"]
+    } else if path.contains("<xdg") {
+        &["linux"]
+    } else {
+        &["windows", "linux", "mac"]
+    }
+}
+
+// Ludusavi paths name files or directories; rogame locations are directories with include
+// globs. A last component with a wildcard or a file extension is treated as a file pattern.
+fn split_file_pattern(path: &str) -> (&str, Option<&str>) {
+    let Some((dir, last)) = path.rsplit_once('/') else {
+        return (path, None);
+    };
+    if last == "*" {
+        return (dir, None);
+    }
+
+    let is_file = last.contains(['*', '?', '['])
+        || last.rsplit_once('.').is_some_and(|(stem, extension)| {
+            !stem.is_empty()
+                && (1..=4).contains(&extension.len())
+                && extension.chars().all(|c| c.is_ascii_alphanumeric())
+        });
+    if is_file && !dir.is_empty() {
+        (dir, Some(last))
+    } else {
+        (path, None)
+    }
+}
+
+// Add a location, merging file patterns into an existing location for the same directory
+fn add_location(locations: &mut Vec<LocationSpec>, path: &str, store: Option<&str>) {
+    let (dir, pattern) = split_file_pattern(path);
+    let store = store.map(str::to_string);
+
+    match locations
+        .iter_mut()
+        .find(|location| location.path == dir && location.store == store)
+    {
+        // An empty include list already saves the whole directory
+        Some(existing) if existing.include.is_empty() => {}
+        Some(existing) => match pattern {
+            Some(pattern) => {
+                if !existing.include.iter().any(|p| p == pattern) {
+                    existing.include.push(pattern.to_string());
+                }
+            }
+            None => existing.include.clear(),
+        },
+        None => locations.push(LocationSpec {
+            path: dir.to_string(),
+            include: pattern.map(|p| vec![p.to_string()]).unwrap_or_default(),
+            exclude: Vec::new(),
+            store,
+        }),
+    }
+}
+
+fn translate_game(name: &str, game: ManifestGame) -> Option<GameEntry> {
+    let steam_id = game
+        .steam
+        .and_then(|steam| steam.id)
+        .map(|id| id.to_string());
+    let mut save_locations = SaveLocations::default();
+
+    for (path, file) in game.files.unwrap_or_default() {
+        let mut conditions = file.and_then(|file| file.when).unwrap_or_default();
+        if conditions.is_empty() {
+            conditions.push(ManifestCondition::default());
+        }
+
+        for condition in conditions {
+            let store = condition.store.as_deref();
+            let Some(translated) = translate_path(&path, store, steam_id.as_deref()) else {
+                continue;
+            };
+
+            let oses = match condition.os.as_deref() {
+                Some(os) => vec![os],
+                None => implied_oses(&path).to_vec(),
+            };
+            for os in oses {
+                let locations = match os {
+                    "windows" => &mut save_locations.windows,
+                    "linux" => &mut save_locations.linux,
+                    "mac" => &mut save_locations.macos,
+                    _ => continue,
+                };
+                add_location(locations, &translated, store);
+            }
+        }
+    }
+
+    if save_locations.windows.is_empty()
+        && save_locations.linux.is_empty()
+        && save_locations.macos.is_empty()
+    {
+        return None;
+    }
+
+    Some(GameEntry {
+        // Without a Steam id, entries get the id the scanner gives the game by its name
+        steam_id: steam_id.unwrap_or_else(|| game_id_from_name(name)),
+        name: name.to_string(),
+        save_locations,
+        save_pattern: vec!["*".to_string()],
+        exclude: Vec::new(),
+    })
+}
+
+// Translate a Ludusavi manifest. Games that fail to parse or have no usable files are
+// skipped; when several games share a Steam id, the first one wins.
+pub(crate) fn parse_manifest(content: &str) -> Result<GameDatabase, String> {
+    let manifest: BTreeMap<String, serde_yaml::Value> = serde_yaml::from_str(content)
+        .map_err(|e| format!("Failed to parse Ludusavi manifest: {}", e))?;
+
+    let mut games = GameDatabase::new();
+    for (name, value) in manifest {
+        let Ok(game) = serde_yaml::from_value::<ManifestGame>(value) else {
+            println!("Skipping unreadable Ludusavi entry: {}", name);
+            continue;
+        };
+        if let Some(entry) = translate_game(&name, game) {
+            games.entry(entry.steam_id.clone()).or_insert(entry);
+        }
+    }
+
+    Ok(games)
+}
+
+fn get_imported_manifest_path() -> Result<PathBuf, String> {
+    Ok(dirs::config_local_dir()
+        .ok_or_else(|| "Failed to get config directory".to_string())?
+        .join("rogame")
+        .join(IMPORTED_MANIFEST))
+}
+
+// The manifest to use: the imported one, otherwise the one Ludusavi keeps up to date
+fn find_manifest() -> Option<PathBuf> {
+    get_imported_manifest_path()
+        .ok()
+        .into_iter()
+        .chain(dirs::config_dir().map(|dir| dir.join("ludusavi").join("manifest.yaml")))
+        .chain(
+            LUDUSAVI_MANIFEST_PATHS
+                .iter()
+                .map(|path| expand_tilde(path)),
+        )
+        .find(|path| path.is_file())
+}
+
+// Entries from the local Ludusavi manifest, if there is one. The parsed manifest is kept
+// until the file changes.
+pub(crate) fn load_manifest() -> Option<Arc<GameDatabase>> {
+    let path = find_manifest()?;
+    let mtime = modified_time(&path)?;
+
+    if let Ok(cache) = MANIFEST_CACHE.lock() {
+        if let Some((cached_path, cached_mtime, games)) = cache.as_ref() {
+            if *cached_path == path && *cached_mtime == mtime {
+                return Some(games.clone());
+            }
+        }
+    }
+
+    println!("Loading Ludusavi manifest from: {:?}", path);
+    let games = match fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read Ludusavi manifest: {}", e))
+        .and_then(|content| parse_manifest(&content))
+    {
+        Ok(games) => Arc::new(games),
+        Err(e) => {
+            println!("{}", e);
+            return None;
+        }
+    };
+    println!("Loaded {} games from Ludusavi manifest", games.len());
+
+    if let Ok(mut cache) = MANIFEST_CACHE.lock() {
+        *cache = Some((path, mtime, games.clone()));
+    }
+    Some(games)
+}
+
+// Import a Ludusavi manifest.yaml into the rogame config dir so it backs the save game
+// database. Returns the number of games it provides.
+#[tauri::command]
+pub async fn import_ludusavi_manifest(path: String) -> Result<usize, String> {
+    println!("Importing Ludusavi manifest from: {}", path);
+    let content = fs::read_to_string(expand_tilde(&path))
+        .map_err(|e| format!("Failed to read Ludusavi manifest: {}", e))?;
+    let games = parse_manifest(&content)?;
+
+    let target = get_imported_manifest_path()?;
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    fs::write(&target, content).map_err(|e| format!("Failed to save Ludusavi manifest: {}", e))?;
+
+    game_database::invalidate();
+    println!("Imported {} games from Ludusavi manifest", games.len());
+    Ok(games.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+Celeste:
+  files:
+    <base>/Saves:
+      when:
+        - os: windows
+        - os: linux
+    <xdgData>/Celeste/Saves:
+      tags:
+        - save
+  installDir:
+    Celeste: {}
+  steam:
+    id: 504230
+Elden Ring:
+  files:
+    <winAppData>/EldenRing/*/ER0000.sl2:
+      tags:
+        - save
+    <winAppData>/EldenRing/*/ER0000.sl2.bak:
+      tags:
+        - save
+    <root>/userdata/<storeUserId>/1245620/remote:
+      when:
+        - store: steam
+    <winPublic>/EldenRing: {}
+  registry:
+    HKEY_CURRENT_USER/Software/EldenRing: {}
+  steam:
+    id: 1245620
+Registry Only:
+  registry:
+    HKEY_CURRENT_USER/Software/Game: {}
+Some GOG Game:
+  files:
+    <home>/.local/share/SomeGame/save.dat:
+      when:
+        - os: linux
+          store: gog
+"#;
+
+    #[test]
+    fn test_parse_manifest() {
+        let games = parse_manifest(MANIFEST).unwrap();
+
+        let mut ids: Vec<&String> = games.keys().collect();
+        ids.sort();
+        assert_eq!(ids, vec!["1245620", "504230", "some_gog_game"]);
+
+        let celeste = &games["504230"];
+        assert_eq!(celeste.name, "Celeste");
+        assert_eq!(
+            celeste
+                .save_locations
+                .linux
+                .iter()
+                .map(|l| l.path.as_str())
+                .collect::<Vec<_>>(),
+            vec!["<installDir>/Saves", "<xdgData>/Celeste/Saves"]
+        );
+        assert_eq!(celeste.save_locations.windows.len(), 1);
+        assert!(celeste.save_locations.macos.is_empty());
+
+        // File rules in the same directory become one location with include globs
+        let elden_ring = &games["1245620"];
+        assert_eq!(
+            elden_ring.save_locations.windows[1],
+            LocationSpec {
+                path: "<winAppData>/EldenRing/*".to_string(),
+                include: vec!["ER0000.sl2".to_string(), "ER0000.sl2.bak".to_string()],
+                exclude: Vec::new(),
+                store: None,
+            }
+        );
+        // Store-specific rules keep their store; <winPublic> has no equivalent
+        let steam_location = &elden_ring.save_locations.linux[0];
+        assert_eq!(
+            steam_location.path,
+            "<steamRoot>/userdata/<steamUserId>/1245620/remote"
+        );
+        assert_eq!(steam_location.store.as_deref(), Some("steam"));
+        assert_eq!(elden_ring.save_locations.windows.len(), 2);
+
+        let gog_game = &games["some_gog_game"];
+        assert_eq!(
+            gog_game.save_locations.linux[0],
+            LocationSpec {
+                path: "<home>/.local/share/SomeGame".to_string(),
+                include: vec!["save.dat".to_string()],
+                exclude: Vec::new(),
+                store: Some("gog".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_translate_path() {
+        assert_eq!(
+            translate_path(
+                "<root>/userdata/<storeUserId>/<storeGameId>/remote",
+                Some("steam"),
+                Some("620")
+            )
+            .as_deref(),
+            Some("<steamRoot>/userdata/<steamUserId>/620/remote")
+        );
+        assert_eq!(translate_path("<root>/saves", Some("gog"), None), None);
+        assert_eq!(
+            translate_path("<winLocalAppData>\\Game\\<storeUserId>", Some("epic"), None).as_deref(),
+            Some("~/AppData/Local/Game/*")
+        );
+        assert_eq!(translate_path("<game>/saves", None, None), None);
+    }
+}
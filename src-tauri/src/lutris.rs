@@ -0,0 +1,235 @@
+use crate::game_scanner::expand_tilde;
+use rusqlite::{Connection, OpenFlags};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Games installed through Lutris, read from its pga.db SQLite database
+
+// Lutris data directories (pga.db) and their matching game config directories
+const LUTRIS_PATHS: &[(&str, &str)] = &[
+    ("~/.local/share/lutris", "~/.config/lutris/games"),
+    (
+        "~/.var/app/net.lutris.Lutris/data/lutris",
+        "~/.var/app/net.lutris.Lutris/config/lutris/games",
+    ),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LutrisGame {
+    pub id: i64,
+    pub name: String,
+    pub slug: String,
+    pub runner: String,
+    pub platform: String,
+    pub directory: Option<PathBuf>,
+    pub config_path: Option<String>,
+    // Set for games imported from Steam (service "steam") or using the steam runner
+    pub steam_id: Option<String>,
+    pub wine_prefix: Option<PathBuf>,
+    pub last_played: Option<i64>,
+}
+
+// Read installed games from an open pga.db connection. Older databases lack the
+// service columns, so they are only queried when present.
+pub fn read_games(conn: &Connection) -> Result<Vec<LutrisGame>, String> {
+    let columns: Vec<String> = conn
+        .prepare("PRAGMA table_info(games)")
+        .and_then(|mut stmt| {
+            stmt.query_map([], |row| row.get::<_, String>(1))?
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(|e| format!("Failed to read Lutris schema: {}", e))?;
+    let has_service =
+        columns.iter().any(|c| c == "service") && columns.iter().any(|c| c == "service_id");
+
+    let query = format!(
+        "SELECT id, name, slug, runner, platform, directory, configpath, lastplayed, {}
+         FROM games
+         WHERE installed = 1",
+        if has_service {
+            "service, service_id"
+        } else {
+            "NULL, NULL"
+        }
+    );
+
+    let mut stmt = conn
+        .prepare(&query)
+        .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+    let games = stmt
+        .query_map([], |row| {
+            let service: Option<String> = row.get(8)?;
+            let service_id: Option<String> = row.get(9)?;
+            Ok(LutrisGame {
+                id: row.get(0)?,
+                name: row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+                slug: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                runner: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+                platform: row.get::<_, Option<String>>(4)?.unwrap_or_default(),
+                directory: row
+                    .get::<_, Option<String>>(5)?
+                    .filter(|d| !d.is_empty())
+                    .map(PathBuf::from),
+                config_path: row.get::<_, Option<String>>(6)?.filter(|c| !c.is_empty()),
+                steam_id: service_id.filter(|_| service.as_deref() == Some("steam")),
+                wine_prefix: None,
+                last_played: row.get::<_, Option<i64>>(7)?.filter(|&t| t > 0),
+            })
+        })
+        .map_err(|e| format!("Failed to query Lutris games: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to collect Lutris games: {}", e))?;
+
+    Ok(games)
+}
+
+// Read `prefix` and `appid` from the `game:` section of a Lutris game config (YAML).
+// Only the flat keys we need are parsed.
+pub fn parse_game_config(content: &str) -> (Option<PathBuf>, Option<String>) {
+    let mut in_game = false;
+    let mut prefix = None;
+    let mut app_id = None;
+
+    for line in content.lines() {
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+
+        if !line.starts_with(' ') && !line.starts_with('\t') {
+            in_game = line.trim_end() == "game:";
+            continue;
+        }
+
+        if !in_game {
+            continue;
+        }
+
+        if let Some((key, value)) = line.trim().split_once(':') {
+            let value = value.trim().trim_matches(|c| c == '\'' || c == '"');
+            match key.trim() {
+                "prefix" if !value.is_empty() => prefix = Some(expand_tilde(value)),
+                "appid" if !value.is_empty() => app_id = Some(value.to_string()),
+                _ => {}
+            }
+        }
+    }
+
+    (prefix, app_id)
+}
+
+fn read_database(db_path: &Path) -> Result<Vec<LutrisGame>, String> {
+    let conn = Connection::open_with_flags(
+        db_path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .map_err(|e| format!("Failed to open Lutris database: {}", e))?;
+
+    read_games(&conn)
+}
+
+// Installed games from every Lutris database on the machine
+pub fn installed_games() -> Vec<LutrisGame> {
+    let mut games: Vec<LutrisGame> = Vec::new();
+
+    for (data_dir, config_dir) in LUTRIS_PATHS {
+        let db_path = expand_tilde(data_dir).join("pga.db");
+        if !db_path.exists() {
+            continue;
+        }
+        println!("Reading Lutris database at: {:?}", db_path);
+
+        let found = match read_database(&db_path) {
+            Ok(found) => found,
+            Err(e) => {
+                println!("{}: {:?}", e, db_path);
+                continue;
+            }
+        };
+
+        for mut game in found {
+            if games.iter().any(|g| g.slug == game.slug) {
+                continue;
+            }
+
+            if let Some(config_path) = &game.config_path {
+                let config_file = expand_tilde(config_dir).join(format!("{}.yml", config_path));
+                if let Ok(content) = fs::read_to_string(&config_file) {
+                    let (prefix, app_id) = parse_game_config(&content);
+                    game.wine_prefix = prefix.filter(|p| p.join("drive_c").is_dir());
+                    if game.steam_id.is_none() && game.runner == "steam" {
+                        game.steam_id = app_id;
+                    }
+                }
+            }
+
+            games.push(game);
+        }
+    }
+
+    games
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_games() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE games (
+                id INTEGER PRIMARY KEY, name TEXT, slug TEXT, runner TEXT, platform TEXT,
+                directory TEXT, configpath TEXT, lastplayed INTEGER, installed INTEGER,
+                service TEXT, service_id TEXT
+            );
+            INSERT INTO games VALUES (1, 'Elden Ring', 'elden-ring', 'wine', 'Windows',
+                '/games/elden-ring', 'elden-ring-1700000000', 1719432010, 1, 'steam', '1245620');
+            INSERT INTO games VALUES (2, 'Celeste', 'celeste', 'linux', 'Linux',
+                '/games/celeste', 'celeste-1700000001', 0, 1, 'gog', '1310535458');
+            INSERT INTO games VALUES (3, 'Removed', 'removed', 'wine', 'Windows',
+                '', NULL, NULL, 0, NULL, NULL);",
+        )
+        .unwrap();
+
+        let games = read_games(&conn).unwrap();
+
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].name, "Elden Ring");
+        assert_eq!(games[0].runner, "wine");
+        assert_eq!(games[0].steam_id.as_deref(), Some("1245620"));
+        assert_eq!(games[0].last_played, Some(1719432010));
+        assert_eq!(games[0].directory, Some(PathBuf::from("/games/elden-ring")));
+        assert_eq!(games[1].steam_id, None);
+        assert_eq!(games[1].last_played, None);
+    }
+
+    #[test]
+    fn test_read_games_legacy_schema() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE games (
+                id INTEGER PRIMARY KEY, name TEXT, slug TEXT, runner TEXT, platform TEXT,
+                directory TEXT, configpath TEXT, lastplayed INTEGER, installed INTEGER
+            );
+            INSERT INTO games VALUES (1, 'Old Game', 'old-game', 'wine', 'Windows',
+                '/games/old', 'old-game-1', NULL, 1);",
+        )
+        .unwrap();
+
+        let games = read_games(&conn).unwrap();
+
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].steam_id, None);
+    }
+
+    #[test]
+    fn test_parse_game_config() {
+        let content = "game:\n  exe: /games/elden-ring/eldenring.exe\n  prefix: '/games/elden-ring/prefix'\n  appid: 1245620\nsystem:\n  prefix: /ignored\nwine:\n  version: lutris-GE\n";
+        let (prefix, app_id) = parse_game_config(content);
+
+        assert_eq!(prefix, Some(PathBuf::from("/games/elden-ring/prefix")));
+        assert_eq!(app_id.as_deref(), Some("1245620"));
+        assert_eq!(parse_game_config("system:\n  prefix: /x\n"), (None, None));
+    }
+}
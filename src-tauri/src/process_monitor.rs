@@ -0,0 +1,448 @@
+use crate::backup_scheduler;
+use crate::db;
+use crate::save_manager;
+use chrono::Utc;
+use rusqlite::params;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+// Detects when library games start and stop by looking for processes whose executable (or,
+// for Wine and Proton games, whose program name, the first command line argument) lies under
+// the game's install directory.
+// When a game exits its play session is recorded and its saves are backed up.
+
+// How often the process table is checked
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub exe: Option<PathBuf>,
+    pub cmdline: Vec<String>,
+}
+
+// Source of running processes, so detection can be tested without a real /proc
+pub trait ProcessTable {
+    fn processes(&self) -> Vec<ProcessInfo>;
+}
+
+// Process table of the running system, read from /proc
+pub struct ProcFs {
+    root: PathBuf,
+}
+
+impl Default for ProcFs {
+    fn default() -> Self {
+        Self {
+            root: PathBuf::from("/proc"),
+        }
+    }
+}
+
+impl ProcessTable for ProcFs {
+    fn processes(&self) -> Vec<ProcessInfo> {
+        let Ok(entries) = fs::read_dir(&self.root) else {
+            return Vec::new();
+        };
+        entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let pid = entry.file_name().to_str()?.parse().ok()?;
+                // Processes of other users or that already exited can't be read
+                let exe = fs::read_link(entry.path().join("exe")).ok();
+                let cmdline = fs::read(entry.path().join("cmdline"))
+                    .map(|data| {
+                        data.split(|&b| b == 0)
+                            .filter(|arg| !arg.is_empty())
+                            .map(|arg| String::from_utf8_lossy(arg).into_owned())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                Some(ProcessInfo { pid, exe, cmdline })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionEvent {
+    Started {
+        game_id: String,
+        started_at: i64,
+    },
+    Stopped {
+        game_id: String,
+        started_at: i64,
+        ended_at: i64,
+    },
+}
+
+// Tracks which games are running between polls of the process table
+pub struct GameDetector<T: ProcessTable> {
+    table: T,
+    // Running games and when they were first seen (Unix ms)
+    running: HashMap<String, i64>,
+}
+
+// A Unix path, or a Windows path on Wine's Z: drive (the Unix root)
+fn cmdline_path(arg: &str) -> Option<PathBuf> {
+    if arg.starts_with('/') {
+        return Some(PathBuf::from(arg));
+    }
+    let rest = arg
+        .strip_prefix("Z:\\")
+        .or_else(|| arg.strip_prefix("z:\\"))?;
+    Some(PathBuf::from(format!("/{}", rest.replace('\\', "/"))))
+}
+
+// Only the program itself counts; other arguments are often files a shell, editor or file
+// manager was pointed at
+fn runs_from(process: &ProcessInfo, install_dir: &Path) -> bool {
+    process
+        .exe
+        .iter()
+        .cloned()
+        .chain(process.cmdline.first().and_then(|arg| cmdline_path(arg)))
+        .any(|path| path.starts_with(install_dir))
+}
+
+impl<T: ProcessTable> GameDetector<T> {
+    pub fn new(table: T) -> Self {
+        Self {
+            table,
+            running: HashMap::new(),
+        }
+    }
+
+    // Compare the running processes with the games' install directories (game id, directory)
+    // and report games that started or stopped since the last poll
+    pub fn poll(&mut self, games: &[(String, PathBuf)], now: i64) -> Vec<SessionEvent> {
+        let processes = self.table.processes();
+        let mut events = Vec::new();
+        for (game_id, install_dir) in games {
+            let is_running = processes
+                .iter()
+                .any(|process| runs_from(process, install_dir));
+            match (is_running, self.running.get(game_id).copied()) {
+                (true, None) => {
+                    self.running.insert(game_id.clone(), now);
+                    events.push(SessionEvent::Started {
+                        game_id: game_id.clone(),
+                        started_at: now,
+                    });
+                }
+                (false, Some(started_at)) => {
+                    self.running.remove(game_id);
+                    events.push(SessionEvent::Stopped {
+                        game_id: game_id.clone(),
+                        started_at,
+                        ended_at: now,
+                    });
+                }
+                _ => {}
+            }
+        }
+        events
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct GameSessionEvent {
+    game_id: String,
+    running: bool,
+    started_at: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ended_at: Option<i64>,
+}
+
+// System directories programs are installed to; a game "installed" there (e.g. a Steam
+// shortcut starting in /usr/bin) would match every program run from it
+const SYSTEM_DIRS: &[&str] = &["/usr", "/bin", "/sbin", "/lib", "/lib64", "/opt", "/etc"];
+
+// Whether processes running from the directory can be taken for the game: not a system
+// directory, the home directory or anything else only a couple of levels below the root
+fn is_game_dir(dir: &Path, home: Option<&Path>) -> bool {
+    let depth = dir
+        .components()
+        .filter(|component| matches!(component, std::path::Component::Normal(_)))
+        .count();
+    dir.is_absolute()
+        && depth >= 3
+        && Some(dir) != home
+        && !SYSTEM_DIRS.iter().any(|system| dir.starts_with(system))
+}
+
+// Install directories worth watching: existing game directories
+fn install_dirs(games: &[save_manager::Game]) -> Vec<(String, PathBuf)> {
+    let home = dirs::home_dir();
+    games
+        .iter()
+        .filter_map(|game| {
+            let dir = PathBuf::from(game.install_path.as_deref()?);
+            (is_game_dir(&dir, home.as_deref()) && dir.is_dir()).then(|| (game.id.clone(), dir))
+        })
+        .collect()
+}
+
+async fn record_session(event: &SessionEvent) -> Result<(), String> {
+    let event = event.clone();
+    db::execute_blocking(move |conn| {
+        match event {
+            SessionEvent::Started {
+                game_id,
+                started_at,
+            } => conn.execute(
+                "UPDATE games SET last_session_start = ?1 WHERE id = ?2",
+                params![started_at, game_id],
+            ),
+            SessionEvent::Stopped {
+                game_id,
+                started_at,
+                ended_at,
+            } => {
+                let last_played = chrono::DateTime::from_timestamp_millis(ended_at)
+                    .map(|t| {
+                        t.with_timezone(&chrono::Local)
+                            .format("%Y-%m-%d %H:%M:%S")
+                            .to_string()
+                    })
+                    .unwrap_or_default();
+                conn.execute(
+                    "UPDATE games SET last_session_start = ?1, last_session_end = ?2,
+                     last_played = ?3 WHERE id = ?4",
+                    params![started_at, ended_at, last_played, game_id],
+                )
+            }
+        }
+        .map_err(|e| format!("Failed to record play session: {}", e))?;
+        Ok(())
+    })
+    .await
+}
+
+// Start watching for library games being launched and closed
+pub fn start(app: AppHandle) {
+    if !Path::new("/proc").is_dir() {
+        println!("No /proc file system, game process monitoring is disabled");
+        return;
+    }
+
+    tauri::async_runtime::spawn(async move {
+        println!("Game process monitor started");
+        let mut detector = GameDetector::new(ProcFs::default());
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            let games = match save_manager::get_all_games().await {
+                Ok(games) => games,
+                Err(e) => {
+                    println!(
+                        "Failed to load games for the process monitor: {}",
+                        e.message
+                    );
+                    continue;
+                }
+            };
+            let titles: HashMap<String, String> = games
+                .iter()
+                .map(|game| (game.id.clone(), game.title.clone()))
+                .collect();
+
+            let events = detector.poll(&install_dirs(&games), Utc::now().timestamp_millis());
+            for event in events {
+                if let Err(e) = record_session(&event).await {
+                    println!("{}", e);
+                }
+                match event {
+                    SessionEvent::Started {
+                        game_id,
+                        started_at,
+                    } => {
+                        println!("Game {} started", game_id);
+                        let _ = app.emit(
+                            "game-session",
+                            GameSessionEvent {
+                                game_id,
+                                running: true,
+                                started_at,
+                                ended_at: None,
+                            },
+                        );
+                    }
+                    SessionEvent::Stopped {
+                        game_id,
+                        started_at,
+                        ended_at,
+                    } => {
+                        println!("Game {} exited, backing up its saves", game_id);
+                        let _ = app.emit(
+                            "game-session",
+                            GameSessionEvent {
+                                game_id: game_id.clone(),
+                                running: false,
+                                started_at,
+                                ended_at: Some(ended_at),
+                            },
+                        );
+                        let title = titles.get(&game_id).cloned().unwrap_or_default();
+                        backup_scheduler::auto_backup(&app, game_id, title, "exit").await;
+                    }
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    struct FakeProcessTable {
+        processes: RefCell<Vec<ProcessInfo>>,
+    }
+
+    impl ProcessTable for &FakeProcessTable {
+        fn processes(&self) -> Vec<ProcessInfo> {
+            self.processes.borrow().clone()
+        }
+    }
+
+    #[test]
+    fn test_detects_native_and_proton_sessions() {
+        let table = FakeProcessTable {
+            processes: RefCell::new(vec![ProcessInfo {
+                pid: 1,
+                exe: Some(PathBuf::from("/usr/bin/bash")),
+                cmdline: vec!["bash".to_string()],
+            }]),
+        };
+        let games = vec![
+            (
+                "1145360".to_string(),
+                PathBuf::from("/games/steamapps/common/Hades"),
+            ),
+            (
+                "1245620".to_string(),
+                PathBuf::from("/games/steamapps/common/ELDEN RING"),
+            ),
+        ];
+        let mut detector = GameDetector::new(&table);
+        assert!(detector.poll(&games, 1_000).is_empty());
+
+        // A native executable, and a Proton game seen through its Wine command line
+        table.processes.borrow_mut().extend([
+            ProcessInfo {
+                pid: 2,
+                exe: Some(PathBuf::from("/games/steamapps/common/Hades/Hades")),
+                cmdline: Vec::new(),
+            },
+            ProcessInfo {
+                pid: 3,
+                exe: Some(PathBuf::from("/usr/bin/wine64-preloader")),
+                cmdline: vec![
+                    "Z:\\games\\steamapps\\common\\ELDEN RING\\Game\\eldenring.exe".to_string(),
+                ],
+            },
+        ]);
+        assert_eq!(
+            detector.poll(&games, 2_000),
+            vec![
+                SessionEvent::Started {
+                    game_id: "1145360".to_string(),
+                    started_at: 2_000,
+                },
+                SessionEvent::Started {
+                    game_id: "1245620".to_string(),
+                    started_at: 2_000,
+                },
+            ]
+        );
+        assert!(detector.poll(&games, 3_000).is_empty());
+
+        table
+            .processes
+            .borrow_mut()
+            .retain(|process| process.pid != 2);
+        assert_eq!(
+            detector.poll(&games, 4_000),
+            vec![SessionEvent::Stopped {
+                game_id: "1145360".to_string(),
+                started_at: 2_000,
+                ended_at: 4_000,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_ignores_paths_passed_to_other_programs() {
+        let table = FakeProcessTable {
+            processes: RefCell::new(Vec::new()),
+        };
+        let games = vec![(
+            "1145360".to_string(),
+            PathBuf::from("/games/steamapps/common/Hades"),
+        )];
+        let mut detector = GameDetector::new(&table);
+
+        // A shell, an editor and a Wine file manager working on files of the game
+        table.processes.borrow_mut().extend([
+            ProcessInfo {
+                pid: 1,
+                exe: Some(PathBuf::from("/usr/bin/ls")),
+                cmdline: vec![
+                    "ls".to_string(),
+                    "/games/steamapps/common/Hades".to_string(),
+                ],
+            },
+            ProcessInfo {
+                pid: 2,
+                exe: Some(PathBuf::from("/usr/bin/vim")),
+                cmdline: vec![
+                    "vim".to_string(),
+                    "/games/steamapps/common/Hades/Content/Subtitles.txt".to_string(),
+                ],
+            },
+            ProcessInfo {
+                pid: 3,
+                exe: Some(PathBuf::from("/usr/bin/wine64-preloader")),
+                cmdline: vec![
+                    "C:\\windows\\explorer.exe".to_string(),
+                    "Z:\\games\\steamapps\\common\\Hades".to_string(),
+                ],
+            },
+        ]);
+        assert!(detector.poll(&games, 1_000).is_empty());
+    }
+
+    #[test]
+    fn test_ignores_system_install_dirs() {
+        let table = FakeProcessTable {
+            processes: RefCell::new(vec![ProcessInfo {
+                pid: 1,
+                exe: Some(PathBuf::from("/usr/bin/bash")),
+                cmdline: vec!["bash".to_string()],
+            }]),
+        };
+        let home = Path::new("/home/player");
+        let games: Vec<(String, PathBuf)> = [
+            ("shortcut_bin", "/usr/bin"),
+            ("shortcut_games", "/usr/games"),
+            ("shortcut_opt", "/opt/tools/bin"),
+            ("shortcut_home", "/home/player"),
+            ("shortcut_mount", "/mnt/games"),
+            ("1145360", "/home/player/Games/Hades"),
+        ]
+        .into_iter()
+        .map(|(game_id, dir)| (game_id.to_string(), PathBuf::from(dir)))
+        .filter(|(_, dir)| is_game_dir(dir, Some(home)))
+        .collect();
+        assert_eq!(games.len(), 1);
+
+        let mut detector = GameDetector::new(&table);
+        assert!(detector.poll(&games, 1_000).is_empty());
+    }
+}
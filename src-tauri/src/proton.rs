@@ -0,0 +1,259 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Resolution of Windows save paths inside Steam Proton (Wine) prefixes.
+// A game's prefix lives at <library>/steamapps/compatdata/<appid>/pfx and its user
+// profile at drive_c/users/steamuser.
+
+const PROTON_USER: &str = "steamuser";
+
+// Windows user folders that moved between Windows/Wine versions
+const FOLDER_ALIASES: &[(&str, &str)] = &[
+    ("My Documents", "Documents"),
+    ("Application Data", "AppData/Roaming"),
+    ("Local Settings/Application Data", "AppData/Local"),
+];
+
+// Find the Proton prefixes (the `pfx` directory) for an app id across all Steam libraries
+pub fn find_prefixes(app_id: &str, library_roots: &[PathBuf]) -> Vec<PathBuf> {
+    library_roots
+        .iter()
+        .map(|root| {
+            root.join("steamapps")
+                .join("compatdata")
+                .join(app_id)
+                .join("pfx")
+        })
+        .filter(|prefix| prefix.join("drive_c").is_dir())
+        .collect()
+}
+
+// Translate a Windows-style save location from save_game_location.json into a path
+// relative to the prefix's drive_c, using forward slashes.
+pub fn windows_path_in_drive_c(windows_path: &str) -> Option<String> {
+    let normalized = windows_path.trim().replace('\\', "/");
+    if normalized.is_empty() {
+        return None;
+    }
+
+    let user = format!("users/{}", PROTON_USER);
+    let lower = normalized.to_ascii_lowercase();
+
+    let env_vars: [(&str, String); 5] = [
+        ("%userprofile%", user.clone()),
+        ("%appdata%", format!("{}/AppData/Roaming", user)),
+        ("%localappdata%", format!("{}/AppData/Local", user)),
+        ("%public%", "users/Public".to_string()),
+        ("%programdata%", "ProgramData".to_string()),
+    ];
+
+    let relative = if let Some(rest) = normalized.strip_prefix("~/") {
+        format!("{}/{}", user, rest)
+    } else if let Some((var, replacement)) = env_vars.iter().find(|(var, _)| lower.starts_with(var))
+    {
+        format!("{}{}", replacement, &normalized[var.len()..])
+    } else if lower.starts_with("c:/users/") {
+        // Swap whatever user name is in the path for the Proton user
+        let rest = &normalized["c:/users/".len()..];
+        match rest.split_once('/') {
+            Some((_, rest)) => format!("{}/{}", user, rest),
+            None => user,
+        }
+    } else if lower.starts_with("c:/") {
+        normalized[3..].to_string()
+    } else {
+        return None;
+    };
+
+    Some(relative.trim_end_matches('/').to_string())
+}
+
+// Resolve `relative` below `base`, matching each component case-insensitively like Windows
+// would. Components containing glob characters are expanded. Returns every existing match.
+pub fn resolve_case_insensitive(base: &Path, relative: &str) -> Vec<PathBuf> {
+    let mut current = vec![base.to_path_buf()];
+
+    for component in relative.split('/').filter(|c| !c.is_empty() && *c != ".") {
+        let mut next = Vec::new();
+        for dir in &current {
+            if component.contains(['*', '?', '[']) {
+                let pattern = dir.join(component).to_string_lossy().into_owned();
+                if let Ok(paths) = glob::glob_with(
+                    &pattern,
+                    glob::MatchOptions {
+                        case_sensitive: false,
+                        ..Default::default()
+                    },
+                ) {
+                    next.extend(paths.filter_map(Result::ok));
+                }
+                continue;
+            }
+
+            let exact = dir.join(component);
+            if exact.exists() {
+                next.push(exact);
+                continue;
+            }
+
+            if let Ok(entries) = fs::read_dir(dir) {
+                next.extend(
+                    entries
+                        .filter_map(|e| e.ok())
+                        .filter(|e| {
+                            e.file_name()
+                                .to_string_lossy()
+                                .eq_ignore_ascii_case(component)
+                        })
+                        .map(|e| e.path()),
+                );
+            }
+        }
+        current = next;
+        if current.is_empty() {
+            break;
+        }
+    }
+
+    current
+}
+
+// Name of the user profile inside a prefix's drive_c/users
+fn prefix_user(drive_c: &Path) -> String {
+    let users = drive_c.join("users");
+    if users.join(PROTON_USER).is_dir() {
+        return PROTON_USER.to_string();
+    }
+
+    if let Ok(user) = std::env::var("USER") {
+        if users.join(&user).is_dir() {
+            return user;
+        }
+    }
+
+    fs::read_dir(&users)
+        .ok()
+        .and_then(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().is_dir())
+                .map(|e| e.file_name().to_string_lossy().into_owned())
+                .find(|name| !name.eq_ignore_ascii_case("Public"))
+        })
+        .unwrap_or_else(|| PROTON_USER.to_string())
+}
+
+// Map a Windows save location into a Proton or Wine prefix, returning the existing directories.
+pub fn resolve_in_prefix(prefix: &Path, windows_path: &str) -> Vec<PathBuf> {
+    let Some(relative) = windows_path_in_drive_c(windows_path) else {
+        return Vec::new();
+    };
+    let drive_c = prefix.join("drive_c");
+
+    // Plain Wine prefixes (Heroic, Lutris) name the profile after the local user
+    let user = prefix_user(&drive_c);
+    let proton_user = format!("users/{}", PROTON_USER);
+    let relative = match relative.strip_prefix(&proton_user) {
+        Some(rest) if user != PROTON_USER => format!("users/{}{}", user, rest),
+        _ => relative,
+    };
+
+    let resolved = resolve_case_insensitive(&drive_c, &relative);
+    if !resolved.is_empty() {
+        return resolved;
+    }
+
+    // Retry with the folder names used by older Windows/Wine layouts
+    for (old, new) in FOLDER_ALIASES {
+        for (from, to) in [(old, new), (new, old)] {
+            let user_from = format!("users/{}/{}", user, from);
+            if relative
+                .to_ascii_lowercase()
+                .starts_with(&user_from.to_ascii_lowercase())
+            {
+                let aliased = format!("users/{}/{}{}", user, to, &relative[user_from.len()..]);
+                let resolved = resolve_case_insensitive(&drive_c, &aliased);
+                if !resolved.is_empty() {
+                    return resolved;
+                }
+            }
+        }
+    }
+
+    Vec::new()
+}
+
+// Resolve a Windows save location for a Steam app id in any of its Proton prefixes
+pub fn resolve_save_location(
+    app_id: &str,
+    windows_path: &str,
+    library_roots: &[PathBuf],
+) -> Vec<PathBuf> {
+    find_prefixes(app_id, library_roots)
+        .iter()
+        .flat_map(|prefix| resolve_in_prefix(prefix, windows_path))
+        .filter(|path| path.is_dir())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_windows_path_in_drive_c() {
+        assert_eq!(
+            windows_path_in_drive_c("~/AppData/Local/SB/Saved/SaveGames").as_deref(),
+            Some("users/steamuser/AppData/Local/SB/Saved/SaveGames")
+        );
+        assert_eq!(
+            windows_path_in_drive_c("~/Documents/The Witcher 3/gamesaves/").as_deref(),
+            Some("users/steamuser/Documents/The Witcher 3/gamesaves")
+        );
+        assert_eq!(
+            windows_path_in_drive_c("%APPDATA%\\EldenRing").as_deref(),
+            Some("users/steamuser/AppData/Roaming/EldenRing")
+        );
+        assert_eq!(
+            windows_path_in_drive_c("C:\\Users\\Bob\\Saved Games\\Game").as_deref(),
+            Some("users/steamuser/Saved Games/Game")
+        );
+        assert_eq!(
+            windows_path_in_drive_c("C:\\ProgramData\\Game").as_deref(),
+            Some("ProgramData/Game")
+        );
+        assert_eq!(windows_path_in_drive_c(""), None);
+        assert_eq!(windows_path_in_drive_c("D:\\Games"), None);
+    }
+
+    #[test]
+    fn test_resolve_in_prefix_case_insensitive() {
+        let root = std::env::temp_dir().join(format!("rogame_proton_{}", uuid::Uuid::new_v4()));
+        let library = root.join("library");
+        let prefix = library.join("steamapps/compatdata/3489700/pfx");
+        let saves = prefix.join("drive_c/users/steamuser/AppData/Local/SB/Saved/SaveGames");
+        fs::create_dir_all(&saves).unwrap();
+        let documents = prefix.join("drive_c/users/steamuser/Documents/My Games");
+        fs::create_dir_all(&documents).unwrap();
+
+        let found = resolve_save_location(
+            "3489700",
+            "~/appdata/local/sb/saved/savegames",
+            &[root.join("missing"), library.clone()],
+        );
+        assert_eq!(found, vec![saves.clone()]);
+
+        // Old "My Documents" name maps onto the current "Documents" folder
+        assert_eq!(
+            resolve_in_prefix(&prefix, "~/My Documents/my games"),
+            vec![documents]
+        );
+        assert_eq!(
+            resolve_in_prefix(&prefix, "~/AppData/Local/sb/*"),
+            vec![prefix.join("drive_c/users/steamuser/AppData/Local/SB/Saved")]
+        );
+        assert!(resolve_save_location("1", "~/AppData", &[library]).is_empty());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
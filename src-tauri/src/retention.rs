@@ -0,0 +1,168 @@
+use chrono::{DateTime, Datelike, Local, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+// Backup retention. Backups are pruned by their recorded creation time using
+// grandfather-father-son rules: the newest `keep_last` backups are kept, plus the newest backup
+// of each of the latest `keep_hourly` hours, `keep_daily` days, `keep_weekly` weeks and
+// `keep_monthly` months that have backups. Pinned backups are always kept and don't count
+// towards any rule.
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    #[serde(default)]
+    pub keep_last: u32,
+    #[serde(default)]
+    pub keep_hourly: u32,
+    #[serde(default)]
+    pub keep_daily: u32,
+    #[serde(default)]
+    pub keep_weekly: u32,
+    #[serde(default)]
+    pub keep_monthly: u32,
+}
+
+// Name of the hour, day, week or month a backup was made in
+type PeriodOf = fn(&DateTime<Local>) -> String;
+
+// A backup as recorded in the save_files table
+#[derive(Debug, Clone)]
+pub struct BackupRecord {
+    pub id: String,
+    pub created_at: Option<DateTime<Utc>>,
+    pub pinned: bool,
+}
+
+impl BackupRecord {
+    pub fn new(id: &str, created_at: &str, pinned: bool) -> Self {
+        Self {
+            id: id.to_string(),
+            created_at: DateTime::parse_from_rfc3339(created_at)
+                .map(|created_at| created_at.with_timezone(&Utc))
+                .ok(),
+            pinned,
+        }
+    }
+}
+
+// Backups a policy removes, newest first
+pub fn expired_backups(backups: &[BackupRecord], policy: &RetentionPolicy) -> Vec<String> {
+    // Backups without a readable creation time sort as the oldest
+    let mut candidates: Vec<&BackupRecord> =
+        backups.iter().filter(|backup| !backup.pinned).collect();
+    candidates.sort_by_key(|backup| std::cmp::Reverse(backup.created_at));
+
+    let mut keep: HashSet<&str> = candidates
+        .iter()
+        .take(policy.keep_last as usize)
+        .map(|backup| backup.id.as_str())
+        .collect();
+
+    let periods: [(u32, PeriodOf); 4] = [
+        (policy.keep_hourly, |t| t.format("%Y-%m-%d %H").to_string()),
+        (policy.keep_daily, |t| t.format("%Y-%m-%d").to_string()),
+        (policy.keep_weekly, |t| {
+            let week = t.iso_week();
+            format!("{}-W{}", week.year(), week.week())
+        }),
+        (policy.keep_monthly, |t| t.format("%Y-%m").to_string()),
+    ];
+    for (count, period_of) in periods {
+        let mut seen = HashSet::new();
+        for backup in &candidates {
+            if seen.len() >= count as usize {
+                break;
+            }
+            let Some(created_at) = backup.created_at else {
+                continue;
+            };
+            // The first backup seen in a period is its newest
+            if seen.insert(period_of(&created_at.with_timezone(&Local))) {
+                keep.insert(backup.id.as_str());
+            }
+        }
+    }
+
+    // A policy that keeps nothing still keeps the newest backup
+    if keep.is_empty() && !backups.iter().any(|backup| backup.pinned) {
+        if let Some(newest) = candidates.first() {
+            keep.insert(newest.id.as_str());
+        }
+    }
+
+    candidates
+        .into_iter()
+        .filter(|backup| !keep.contains(backup.id.as_str()))
+        .map(|backup| backup.id.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn test_grandfather_father_son_rotation() {
+        // One backup every 6 hours for 20 days, newest first
+        let newest = Local.with_ymd_and_hms(2024, 6, 19, 18, 0, 0).unwrap();
+        let backups: Vec<BackupRecord> = (0..80)
+            .map(|i| {
+                let created_at = newest - Duration::hours(6 * i);
+                BackupRecord::new(
+                    &format!("backup_{}", i),
+                    &created_at.with_timezone(&Utc).to_rfc3339(),
+                    i == 79,
+                )
+            })
+            .collect();
+        let kept = |policy: &RetentionPolicy| {
+            let expired = expired_backups(&backups, policy);
+            backups
+                .iter()
+                .map(|backup| backup.id.as_str())
+                .filter(|id| !expired.iter().any(|expired| expired == id))
+                .collect::<Vec<_>>()
+        };
+
+        let last_three = RetentionPolicy {
+            keep_last: 3,
+            ..Default::default()
+        };
+        assert_eq!(
+            kept(&last_three),
+            vec!["backup_0", "backup_1", "backup_2", "backup_79"]
+        );
+
+        // The newest backup of each of the last 3 days, on top of the newest 2 backups
+        let daily = RetentionPolicy {
+            keep_last: 2,
+            keep_daily: 3,
+            ..Default::default()
+        };
+        assert_eq!(
+            kept(&daily),
+            vec!["backup_0", "backup_1", "backup_4", "backup_8", "backup_79"]
+        );
+
+        let weekly = RetentionPolicy {
+            keep_weekly: 2,
+            ..Default::default()
+        };
+        assert_eq!(kept(&weekly), vec!["backup_0", "backup_12", "backup_79"]);
+
+        // Without rules only the newest backup is kept
+        let unpinned: Vec<BackupRecord> = backups
+            .iter()
+            .cloned()
+            .map(|backup| BackupRecord {
+                pinned: false,
+                ..backup
+            })
+            .collect();
+        assert_eq!(
+            expired_backups(&unpinned, &RetentionPolicy::default()).len(),
+            79
+        );
+    }
+}
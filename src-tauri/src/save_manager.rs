@@ -1,101 +1,23 @@
 use chrono::prelude::*;
+use once_cell::sync::Lazy;
 use rusqlite::params;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs::{self, create_dir_all};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
+use crate::backup_scheduler;
+use crate::backup_store;
 use crate::db;
+use crate::game_database;
+use crate::game_identity::{self, StoreId};
+use crate::game_scanner::{self, SteamAccount, SteamLibraries};
+use crate::retention::{self, BackupRecord, RetentionPolicy};
+use crate::save_paths::{self, GameEntry, ResolveContext, ResolvedLocation};
+use crate::save_watcher;
 use crate::security::{safe_join_path, safe_expand_tilde, validate_path_component};
 
-// Structs for the new JSON structure
-#[derive(Debug, Deserialize)]
-struct GameEntry {
-    steam_id: String,
-    name: String,
-    save_locations: SaveLocations,
-    save_pattern: Vec<String>,
-}
-
-#[derive(Debug, Deserialize)]
-struct SaveLocations {
-    #[serde(default)]
-    macos: String,
-    #[serde(default)]
-    windows: String,
-    #[serde(default)]
-    linux: String,
-}
-
-// Helper function to get platform-specific save location
-fn get_platform_save_location(locations: &SaveLocations) -> &str {
-    #[cfg(target_os = "macos")]
-    return &locations.macos;
-
-    #[cfg(target_os = "windows")]
-    return &locations.windows;
-
-    #[cfg(target_os = "linux")]
-    return &locations.linux;
-
-    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
-    return "";
-}
-
-// Helper function to get save game config path
-fn get_save_config_path() -> PathBuf {
-    if cfg!(debug_assertions) {
-        // During development, use the source file
-        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/save_game_location.json")
-    } else {
-        // In production, load from resources
-        if let Ok(exe_path) = std::env::current_exe() {
-            if let Some(exe_dir) = exe_path.parent() {
-                // Try multiple possible locations based on platform
-                let mut resource_paths = vec![];
-                
-                #[cfg(target_os = "macos")]
-                {
-                    // On macOS, resources are in Contents/Resources
-                    if let Some(contents_dir) = exe_dir.parent() {
-                        if let Some(_app_dir) = contents_dir.parent() {
-                            resource_paths.push(contents_dir.join("Resources").join("save_game_location.json"));
-                        }
-                    }
-                }
-                
-                #[cfg(target_os = "windows")]
-                {
-                    // On Windows, resources are typically in the same directory as the exe
-                    resource_paths.push(exe_dir.join("save_game_location.json"));
-                    resource_paths.push(exe_dir.join("resources").join("save_game_location.json"));
-                }
-                
-                #[cfg(target_os = "linux")]
-                {
-                    // On Linux, could be in various locations
-                    resource_paths.push(exe_dir.join("save_game_location.json"));
-                    resource_paths.push(exe_dir.join("resources").join("save_game_location.json"));
-                    // AppImage structure
-                    resource_paths.push(exe_dir.join("../lib/rogame").join("save_game_location.json"));
-                }
-                
-                // Common fallback paths
-                resource_paths.push(exe_dir.join("_up_").join("Resources").join("save_game_location.json"));
-
-                for path in &resource_paths {
-                    if path.exists() {
-                        return path.clone();
-                    }
-                }
-            }
-        }
-
-        // Fallback
-        PathBuf::from("save_game_location.json")
-    }
-}
-
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SaveFile {
     pub id: String,
@@ -108,6 +30,24 @@ pub struct SaveFile {
     pub file_path: String,
     pub origin_path: String,
     pub cloud: Option<String>, // "gdrive", "dropbox", "onedrive", etc.
+    // Steam account the backup was taken from, for saves stored per account
+    #[serde(default)]
+    pub steam_account_id: Option<String>,
+    #[serde(default)]
+    pub steam_persona_name: Option<String>,
+    // Result of the last integrity check: "ok", "missing", "corrupt" or "unverifiable"
+    #[serde(default)]
+    pub verification_status: Option<String>,
+    #[serde(default)]
+    pub verified_at: Option<String>,
+    // Pinned backups are never removed by the retention policy
+    #[serde(default)]
+    pub pinned: bool,
+    // Short name and free-text notes given to the backup
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -125,6 +65,32 @@ pub struct Game {
     pub save_location: String,
     pub backup_location: Option<String>,
     pub last_backup_time: Option<i64>,
+    // Files to back up, overriding the game database's save pattern
+    #[serde(default)]
+    pub save_pattern: Option<Vec<String>>,
+    // Files never backed up or restored, on top of the game database's exclusions
+    #[serde(default)]
+    pub exclude_patterns: Vec<String>,
+    // Auto-backup interval overriding the backup settings, e.g. "1hour" or "off"
+    #[serde(default)]
+    pub backup_interval: Option<String>,
+    // Back up as soon as the game writes its saves
+    #[serde(default)]
+    pub watch_saves: bool,
+    // Directory the game is installed in, used to detect when it is running
+    #[serde(default)]
+    pub install_path: Option<String>,
+    // Last play session seen by the process monitor (Unix timestamps in milliseconds)
+    #[serde(default)]
+    pub last_session_start: Option<i64>,
+    #[serde(default)]
+    pub last_session_end: Option<i64>,
+    // Retention rules overriding the backup settings
+    #[serde(default)]
+    pub retention_policy: Option<RetentionPolicy>,
+    // Key of the game's entry in the save game database, whichever store it came from
+    #[serde(default)]
+    pub database_id: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -137,6 +103,8 @@ pub struct BackupResponse {
     pub save_file: SaveFile,
     pub backup_time: i64, // Unix timestamp in milliseconds
     pub save_count: i32,  // Current number of saves
+    // False when the saves were unchanged and the latest backup was returned instead
+    pub created: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -145,6 +113,32 @@ pub struct BackupSettings {
     pub backup_interval: String,
     pub max_backups: i32,
     pub compression_enabled: bool,
+    // Algorithm used when compression is enabled: "zstd" or "zip" (deflate)
+    #[serde(default = "default_compression_algorithm")]
+    pub compression_algorithm: backup_store::Encoding,
+    // Algorithm specific level; the algorithm's default when not set
+    #[serde(default)]
+    pub compression_level: Option<i32>,
+    // Seconds a watched save location must stay unchanged before it is backed up
+    #[serde(default = "default_watch_quiet_seconds")]
+    pub watch_quiet_seconds: u64,
+    // Grandfather-father-son retention on top of the newest `max_backups` backups
+    #[serde(default)]
+    pub keep_hourly: u32,
+    #[serde(default)]
+    pub keep_daily: u32,
+    #[serde(default)]
+    pub keep_weekly: u32,
+    #[serde(default)]
+    pub keep_monthly: u32,
+}
+
+fn default_watch_quiet_seconds() -> u64 {
+    10
+}
+
+fn default_compression_algorithm() -> backup_store::Encoding {
+    backup_store::Encoding::Zstd
 }
 
 impl Default for BackupSettings {
@@ -154,6 +148,40 @@ impl Default for BackupSettings {
             backup_interval: "30min".to_string(),
             max_backups: 5,
             compression_enabled: true,
+            compression_algorithm: default_compression_algorithm(),
+            compression_level: None,
+            watch_quiet_seconds: default_watch_quiet_seconds(),
+            keep_hourly: 0,
+            keep_daily: 0,
+            keep_weekly: 0,
+            keep_monthly: 0,
+        }
+    }
+}
+
+impl BackupSettings {
+    pub fn retention_policy(&self) -> RetentionPolicy {
+        RetentionPolicy {
+            keep_last: self.max_backups.max(0) as u32,
+            keep_hourly: self.keep_hourly,
+            keep_daily: self.keep_daily,
+            keep_weekly: self.keep_weekly,
+            keep_monthly: self.keep_monthly,
+        }
+    }
+
+    // Compression for new backup objects, with the level clamped to what the algorithm supports
+    pub fn compression(&self) -> backup_store::Compression {
+        if !self.compression_enabled {
+            return backup_store::Compression::None;
+        }
+        match self.compression_algorithm {
+            backup_store::Encoding::Deflate => backup_store::Compression::Deflate(
+                self.compression_level.unwrap_or(6).clamp(0, 9) as u32,
+            ),
+            backup_store::Encoding::Zstd => {
+                backup_store::Compression::Zstd(self.compression_level.unwrap_or(3).clamp(1, 22))
+            }
         }
     }
 }
@@ -201,10 +229,73 @@ impl SaveFile {
             file_path: expanded_path,
             origin_path: expanded_origin_path,
             cloud: None,
+            steam_account_id: None,
+            steam_persona_name: None,
+            verification_status: None,
+            verified_at: None,
+            pinned: false,
+            label: None,
+            notes: None,
+        }
+    }
+}
+
+
+// Glob and tag lists are stored as JSON arrays; NULL means not set
+fn parse_patterns(value: Option<String>) -> Option<Vec<String>> {
+    value.and_then(|value| serde_json::from_str(&value).ok())
+}
+
+fn format_patterns(patterns: &[String]) -> Option<String> {
+    if patterns.is_empty() {
+        None
+    } else {
+        serde_json::to_string(patterns).ok()
+    }
+}
+
+// Tags without surrounding whitespace, empty tags or duplicates (ignoring case)
+fn clean_tags(tags: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim();
+        if !tag.is_empty() && !cleaned.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            cleaned.push(tag.to_string());
         }
     }
+    cleaned
 }
 
+// Globs given as a list or a comma separated string ("*.sav, *.cfg")
+fn patterns_from_value(value: &serde_json::Value) -> Option<Vec<String>> {
+    let patterns: Vec<String> = match value {
+        serde_json::Value::String(text) => text.split(',').map(str::to_string).collect(),
+        serde_json::Value::Array(items) => items
+            .iter()
+            .filter_map(|item| item.as_str().map(str::to_string))
+            .collect(),
+        _ => return None,
+    };
+    Some(clean_patterns(patterns)).filter(|patterns| !patterns.is_empty())
+}
+
+fn clean_patterns(patterns: Vec<String>) -> Vec<String> {
+    patterns
+        .into_iter()
+        .map(|pattern| pattern.trim().to_string())
+        .filter(|pattern| !pattern.is_empty())
+        .collect()
+}
+
+// Check that every glob parses, so bad rules are rejected instead of silently matching nothing
+fn validate_patterns(patterns: &[String]) -> Result<(), SaveFileError> {
+    for pattern in patterns {
+        glob::Pattern::new(pattern).map_err(|e| SaveFileError {
+            message: format!("Invalid pattern '{}': {}", pattern, e),
+        })?;
+    }
+    Ok(())
+}
 
 #[tauri::command]
 pub async fn get_all_games() -> Result<Vec<Game>, SaveFileError> {
@@ -229,6 +320,17 @@ pub async fn get_all_games() -> Result<Vec<Game>, SaveFileError> {
                     save_location: row.get(10)?,
                     backup_location: row.get(11)?,
                     last_backup_time: row.get(12)?,
+                    save_pattern: parse_patterns(row.get(13)?),
+                    exclude_patterns: parse_patterns(row.get(14)?).unwrap_or_default(),
+                    backup_interval: row.get(15)?,
+                    watch_saves: row.get(16)?,
+                    install_path: row.get(17)?,
+                    last_session_start: row.get(18)?,
+                    last_session_end: row.get(19)?,
+                    retention_policy: row
+                        .get::<_, Option<String>>(20)?
+                        .and_then(|policy| serde_json::from_str(&policy).ok()),
+                    database_id: row.get(21)?,
                 })
             })
             .map_err(|e| format!("Failed to query games: {}", e))?
@@ -266,6 +368,17 @@ pub async fn get_game_by_id(id: String) -> Result<Game, SaveFileError> {
                     save_location: row.get(10)?,
                     backup_location: row.get(11)?,
                     last_backup_time: row.get(12)?,
+                    save_pattern: parse_patterns(row.get(13)?),
+                    exclude_patterns: parse_patterns(row.get(14)?).unwrap_or_default(),
+                    backup_interval: row.get(15)?,
+                    watch_saves: row.get(16)?,
+                    install_path: row.get(17)?,
+                    last_session_start: row.get(18)?,
+                    last_session_end: row.get(19)?,
+                    retention_policy: row
+                        .get::<_, Option<String>>(20)?
+                        .and_then(|policy| serde_json::from_str(&policy).ok()),
+                    database_id: row.get(21)?,
                 })
             })
             .map_err(|e| format!("Failed to get game: {}", e))?;
@@ -283,8 +396,11 @@ pub async fn add_game(game: Game) -> Result<(), SaveFileError> {
             "INSERT INTO games (
                 id, title, cover_image, platform, last_played, save_count, 
                 size, status, category, is_favorite, save_location, 
-                backup_location, last_backup_time
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                backup_location, last_backup_time, save_pattern, exclude_patterns,
+                backup_interval, watch_saves, install_path, last_session_start, last_session_end,
+                retention_policy, database_id
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17,
+                ?18, ?19, ?20, ?21, ?22)",
             params![
                 game.id,
                 game.title,
@@ -299,6 +415,17 @@ pub async fn add_game(game: Game) -> Result<(), SaveFileError> {
                 game.save_location,
                 game.backup_location,
                 game.last_backup_time,
+                game.save_pattern.as_deref().and_then(format_patterns),
+                format_patterns(&game.exclude_patterns),
+                game.backup_interval,
+                game.watch_saves,
+                game.install_path,
+                game.last_session_start,
+                game.last_session_end,
+                game.retention_policy
+                    .as_ref()
+                    .and_then(|policy| serde_json::to_string(policy).ok()),
+                game.database_id,
             ],
         )
         .map_err(|e| format!("Failed to add game: {}", e))?;
@@ -306,7 +433,10 @@ pub async fn add_game(game: Game) -> Result<(), SaveFileError> {
         Ok(())
     })
     .await
-    .map_err(|e| SaveFileError { message: e })
+    .map_err(|e| SaveFileError { message: e })?;
+
+    save_watcher::refresh().await;
+    Ok(())
 }
 
 #[tauri::command]
@@ -348,19 +478,160 @@ pub async fn update_game(game: Game) -> Result<(), SaveFileError> {
         Ok(())
     })
     .await
-    .map_err(|e| SaveFileError { message: e })
+    .map_err(|e| SaveFileError { message: e })?;
+
+    save_watcher::refresh().await;
+    Ok(())
 }
 
 #[tauri::command]
 pub async fn delete_game(id: String) -> Result<(), SaveFileError> {
     db::execute_blocking(move |conn| {
+        game_identity::remove_store_ids(conn, &id)?;
         conn.execute("DELETE FROM games WHERE id = ?1", params![id])
             .map_err(|e| format!("Failed to delete game: {}", e))?;
 
         Ok(())
     })
     .await
-    .map_err(|e| SaveFileError { message: e })
+    .map_err(|e| SaveFileError { message: e })?;
+
+    save_watcher::refresh().await;
+    Ok(())
+}
+
+// Set the files backed up for a game: `save_pattern` replaces the game database's pattern
+// (None restores it) and `exclude_patterns` is the game's own exclusion list
+#[tauri::command]
+pub async fn update_game_save_rules(
+    game_id: String,
+    save_pattern: Option<Vec<String>>,
+    exclude_patterns: Vec<String>,
+) -> Result<Game, SaveFileError> {
+    let save_pattern = save_pattern
+        .map(clean_patterns)
+        .filter(|patterns| !patterns.is_empty());
+    let exclude_patterns = clean_patterns(exclude_patterns);
+    validate_patterns(save_pattern.as_deref().unwrap_or_default())?;
+    validate_patterns(&exclude_patterns)?;
+
+    println!(
+        "Updating save rules of {}: pattern {:?}, excluding {:?}",
+        game_id, save_pattern, exclude_patterns
+    );
+    let id = game_id.clone();
+    db::execute_blocking(move |conn| {
+        let updated = conn
+            .execute(
+                "UPDATE games SET save_pattern = ?1, exclude_patterns = ?2 WHERE id = ?3",
+                params![
+                    save_pattern.as_deref().and_then(format_patterns),
+                    format_patterns(&exclude_patterns),
+                    id
+                ],
+            )
+            .map_err(|e| format!("Failed to update save rules: {}", e))?;
+        if updated == 0 {
+            return Err(format!("Game not found: {}", id));
+        }
+        Ok(())
+    })
+    .await
+    .map_err(|e| SaveFileError { message: e })?;
+
+    get_game_by_id(game_id).await
+}
+
+// Set how often a game is backed up automatically; None follows the backup settings
+#[tauri::command]
+pub async fn update_game_backup_interval(
+    game_id: String,
+    backup_interval: Option<String>,
+) -> Result<Game, SaveFileError> {
+    let backup_interval = backup_interval
+        .map(|interval| interval.trim().to_string())
+        .filter(|interval| !interval.is_empty());
+    if let Some(interval) = &backup_interval {
+        backup_scheduler::parse_interval(interval).map_err(|e| SaveFileError { message: e })?;
+    }
+
+    println!(
+        "Updating backup interval of {}: {:?}",
+        game_id, backup_interval
+    );
+    let id = game_id.clone();
+    db::execute_blocking(move |conn| {
+        let updated = conn
+            .execute(
+                "UPDATE games SET backup_interval = ?1 WHERE id = ?2",
+                params![backup_interval, id],
+            )
+            .map_err(|e| format!("Failed to update backup interval: {}", e))?;
+        if updated == 0 {
+            return Err(format!("Game not found: {}", id));
+        }
+        Ok(())
+    })
+    .await
+    .map_err(|e| SaveFileError { message: e })?;
+
+    get_game_by_id(game_id).await
+}
+
+// Enable or disable backing up a game whenever it writes its saves
+#[tauri::command]
+pub async fn set_game_watch_saves(game_id: String, enabled: bool) -> Result<Game, SaveFileError> {
+    println!("Setting save watching of {}: {}", game_id, enabled);
+    let id = game_id.clone();
+    db::execute_blocking(move |conn| {
+        let updated = conn
+            .execute(
+                "UPDATE games SET watch_saves = ?1 WHERE id = ?2",
+                params![enabled, id],
+            )
+            .map_err(|e| format!("Failed to update save watching: {}", e))?;
+        if updated == 0 {
+            return Err(format!("Game not found: {}", id));
+        }
+        Ok(())
+    })
+    .await
+    .map_err(|e| SaveFileError { message: e })?;
+
+    save_watcher::refresh().await;
+    get_game_by_id(game_id).await
+}
+
+// Set a game's own retention rules; None follows the backup settings
+#[tauri::command]
+pub async fn update_game_retention_policy(
+    game_id: String,
+    policy: Option<RetentionPolicy>,
+) -> Result<Game, SaveFileError> {
+    println!("Updating retention policy of {}: {:?}", game_id, policy);
+    let policy = policy
+        .map(|policy| serde_json::to_string(&policy))
+        .transpose()
+        .map_err(|e| SaveFileError {
+            message: format!("Failed to serialize retention policy: {}", e),
+        })?;
+    let id = game_id.clone();
+    db::execute_blocking(move |conn| {
+        let updated = conn
+            .execute(
+                "UPDATE games SET retention_policy = ?1 WHERE id = ?2",
+                params![policy, id],
+            )
+            .map_err(|e| format!("Failed to update retention policy: {}", e))?;
+        if updated == 0 {
+            return Err(format!("Game not found: {}", id));
+        }
+        Ok(())
+    })
+    .await
+    .map_err(|e| SaveFileError { message: e })?;
+
+    get_game_by_id(game_id).await
 }
 
 #[tauri::command]
@@ -395,7 +666,11 @@ pub async fn toggle_favorite(id: String) -> Result<Game, SaveFileError> {
 }
 
 #[tauri::command]
-pub async fn restore_save(game_id: String, save_id: String) -> Result<SaveFile, SaveFileError> {
+pub async fn restore_save(
+    game_id: String,
+    save_id: String,
+    steam_account_id: Option<String>,
+) -> Result<SaveFile, SaveFileError> {
     println!(
         "Attempting to restore save. Game ID: {}, Save ID: {}",
         game_id, save_id
@@ -408,10 +683,19 @@ pub async fn restore_save(game_id: String, save_id: String) -> Result<SaveFile,
     let saves_dir = get_saves_directory()?;
     let game_saves_dir = safe_join_path(&saves_dir, &game_id)?;
     let save_path = safe_join_path(&game_saves_dir, &save_id)?;
+    // Backups in the backup store are manifests; older ones are plain directories
+    let manifest_path = backup_store::manifest_path(&game_saves_dir, &save_id);
+    let manifest = if manifest_path.exists() {
+        Some(backup_store::read_manifest(&manifest_path).map_err(|e| SaveFileError {
+            message: format!("Failed to read backup manifest: {}", e),
+        })?)
+    } else {
+        None
+    };
 
     println!("Backup file path: {:?}", save_path);
 
-    if !save_path.exists() {
+    if manifest.is_none() && !save_path.exists() {
         return Err(SaveFileError {
             message: format!("Save file not found at path: {:?}", save_path),
         });
@@ -419,6 +703,7 @@ pub async fn restore_save(game_id: String, save_id: String) -> Result<SaveFile,
 
     // Get the game from database to get the actual save location
     let game = get_game_by_id(game_id.clone()).await?;
+    let steam_libraries = SteamLibraries::load();
 
     // Get the origin path (where to restore the save)
     let save_location = if !game.save_location.is_empty() {
@@ -426,7 +711,7 @@ pub async fn restore_save(game_id: String, save_id: String) -> Result<SaveFile,
     } else {
         // Try to get from JSON config if not in database
         println!("No save location in database, checking JSON config...");
-        match get_save_location_from_config(&game_id) {
+        match get_save_location_from_config(&game, &steam_libraries) {
             Ok(location) => {
                 println!("Found save location in config: {}", location);
                 location
@@ -439,10 +724,48 @@ pub async fn restore_save(game_id: String, save_id: String) -> Result<SaveFile,
         }
     };
 
+    // Per-account saves go to the chosen account, defaulting to the one the backup came from
+    let recorded_account = get_save_file_steam_account(game_id.clone(), save_id.clone()).await?;
+    let target_account = steam_account_id.or_else(|| recorded_account.clone());
+    let (save_location, save_path) = match target_account
+        .as_deref()
+        .map(|account_id| select_steam_account(&save_location, Some(account_id)))
+        .transpose()?
+        .flatten()
+    {
+        Some((location, account)) => {
+            println!(
+                "Restoring to Steam account {} ({})",
+                account.account_id, account.persona_name
+            );
+            // Backups taken before accounts were tracked hold the matched folder by name
+            let legacy_path = safe_expand_tilde(&location)?
+                .file_name()
+                .map(|name| save_path.join(name))
+                .filter(|path| recorded_account.is_none() && path.is_dir());
+            (location, legacy_path.unwrap_or(save_path))
+        }
+        None => (save_location, save_path),
+    };
+
     let origin_path = safe_expand_tilde(&save_location)?;
+    let filters = get_save_location_filters(&game, &save_location, &steam_libraries);
 
-    // Check if the backup is a directory (for pattern "*" backups)
-    if save_path.is_dir() {
+    let restored_size = if let Some(manifest) = &manifest {
+        println!(
+            "Restoring {} files from {:?} to {:?}",
+            manifest.files.len(),
+            manifest_path,
+            origin_path
+        );
+        backup_store::restore_backup(&saves_dir, manifest, &origin_path, |relative| {
+            filters.is_included(relative)
+        })
+        .map_err(|e| SaveFileError {
+            message: format!("Failed to restore backup: {}", e),
+        })?
+    } else if save_path.is_dir() {
+        // Check if the backup is a directory (for pattern "*" backups)
         println!(
             "Restoring directory backup from {:?} to {:?}",
             save_path, origin_path
@@ -466,15 +789,20 @@ pub async fn restore_save(game_id: String, save_id: String) -> Result<SaveFile,
         //     })?;
         // }
 
-        // Copy all files from backup to origin
-        copy_dir_recursive(&save_path, &origin_path).map_err(|e| SaveFileError {
+        // Copy the backed up files matching the game's save rules to origin
+        copy_save_dir(&save_path, &origin_path, &filters).map_err(|e| SaveFileError {
             message: format!("Failed to restore directory: {}", e),
-        })?;
+        })?
     } else {
         // Single file restore (legacy behavior)
         let file_name = save_path.file_name().ok_or_else(|| SaveFileError {
             message: "Invalid save file name".to_string(),
         })?;
+        if filters.is_excluded(Path::new(file_name)) {
+            return Err(SaveFileError {
+                message: format!("Save file {:?} is excluded by the game's save rules", file_name),
+            });
+        }
 
         let target_path = origin_path.join(file_name);
 
@@ -488,16 +816,17 @@ pub async fn restore_save(game_id: String, save_id: String) -> Result<SaveFile,
         // Copy the file
         fs::copy(&save_path, &target_path).map_err(|e| SaveFileError {
             message: format!("Failed to restore save file: {}", e),
-        })?;
-    }
-
-    let metadata = fs::metadata(&save_path).map_err(|e| SaveFileError {
-        message: format!("Failed to read save file metadata: {}", e),
-    })?;
+        })?
+    };
+    let restored_from = if manifest.is_some() {
+        manifest_path
+    } else {
+        save_path
+    };
 
     println!(
         "Successfully restored save file from {:?} to {:?}",
-        save_path, origin_path
+        restored_from, origin_path
     );
 
     // Update the game's last_played field in the database
@@ -519,48 +848,65 @@ pub async fn restore_save(game_id: String, save_id: String) -> Result<SaveFile,
     Ok(SaveFile::new(
         game_id,
         save_id,
-        metadata.len(),
-        save_path.to_string_lossy().into_owned(),
+        restored_size,
+        restored_from.to_string_lossy().into_owned(),
         origin_path.to_string_lossy().into_owned(),
     ))
 }
 
-// Helper function to get save location from JSON if not in database
-fn get_save_location_from_config(game_id: &str) -> Result<String, SaveFileError> {
-    println!("Looking up save location for game_id: {}", game_id);
-
-    let config_path = get_save_config_path();
-    println!("Reading config from: {:?}", config_path);
+fn load_game_config() -> Result<Arc<HashMap<String, GameEntry>>, SaveFileError> {
+    game_database::load().map_err(|e| SaveFileError { message: e })
+}
 
-    let config_content = match fs::read_to_string(&config_path) {
-        Ok(content) => content,
-        Err(e) => {
-            println!("Failed to read config file: {}, using embedded config", e);
-            // Use embedded config as fallback
-            include_str!("save_game_location.json").to_string()
-        }
-    };
+// Helper function to get save location from JSON if not in database
+fn get_save_location_from_config(
+    game: &Game,
+    steam_libraries: &SteamLibraries,
+) -> Result<String, SaveFileError> {
+    get_config_location(game, steam_libraries).map(|location| location.path)
+}
 
-    let game_config: HashMap<String, GameEntry> =
-        serde_json::from_str(&config_content).map_err(|e| SaveFileError {
-            message: format!("Failed to parse save game configuration: {}", e),
-        })?;
+// Resolve the game's configured locations: the first one holding data, then a Proton
+// prefix, then the first configured location
+fn get_config_location(
+    game: &Game,
+    steam_libraries: &SteamLibraries,
+) -> Result<ResolvedLocation, SaveFileError> {
+    let game_id = game.id.as_str();
+    println!("Looking up save location for game_id: {}", game_id);
 
-    // Extract steam_id from game_id (remove epic_ prefix if present)
-    let steam_id = if game_id.starts_with("epic_") {
-        game_id.strip_prefix("epic_").unwrap_or(game_id)
-    } else {
-        game_id
+    let Some(steam_id) = game.database_id.as_deref() else {
+        return Err(SaveFileError {
+            message: format!("Game '{}' is not in the save game database", game_id),
+        });
     };
+    let game_config = load_game_config()?;
 
     println!("Looking for steam_id: {} in config", steam_id);
 
     if let Some(game_entry) = game_config.get(steam_id) {
         println!("Found game entry: {}", game_entry.name);
-        let save_location = get_platform_save_location(&game_entry.save_locations);
-        if !save_location.is_empty() {
-            println!("Save location found: {}", save_location);
-            Ok(save_location.to_string())
+        // Locations inside a Steam installation (userdata, ...) are resolved against the
+        // Steam root the game is installed in, which may be a Flatpak or Snap install
+        let locations = save_paths::resolve_locations(
+            game_entry,
+            &ResolveContext {
+                steam_root: steam_libraries.root_for_app(steam_id),
+                ..Default::default()
+            },
+        );
+
+        if let Some(location) = locations
+            .iter()
+            .find(|location| save_location_exists(&location.path))
+        {
+            println!("Save location found: {}", location.path);
+            Ok(location.clone())
+        } else if let Some(proton_location) = get_proton_save_location(game, steam_libraries) {
+            Ok(proton_location)
+        } else if let Some(location) = locations.into_iter().next() {
+            println!("Save location found: {}", location.path);
+            Ok(location)
         } else {
             Err(SaveFileError {
                 message: format!(
@@ -585,6 +931,224 @@ fn get_save_location_from_config(game_id: &str) -> Result<String, SaveFileError>
     }
 }
 
+// Include/exclude globs for a save location: those of the matching configured location,
+// otherwise the game's defaults, with the game's own rules from the library applied on top.
+// Games without a config entry back up everything not excluded.
+fn get_save_location_filters(
+    game: &Game,
+    save_location: &str,
+    steam_libraries: &SteamLibraries,
+) -> ResolvedLocation {
+    let mut filters = get_config_filters(game, save_location, steam_libraries);
+    if let Some(save_pattern) = game.save_pattern.as_ref().filter(|p| !p.is_empty()) {
+        filters.include = save_pattern.clone();
+    }
+    for pattern in &game.exclude_patterns {
+        if !filters.exclude.contains(pattern) {
+            filters.exclude.push(pattern.clone());
+        }
+    }
+    filters
+}
+
+fn get_config_filters(
+    game: &Game,
+    save_location: &str,
+    steam_libraries: &SteamLibraries,
+) -> ResolvedLocation {
+    let database_id = game.database_id.as_deref().unwrap_or_default();
+    let entry = load_game_config()
+        .ok()
+        .and_then(|config| config.get(database_id).cloned());
+    let Some(entry) = entry else {
+        return ResolvedLocation {
+            path: save_location.to_string(),
+            include: vec!["*".to_string()],
+            exclude: Vec::new(),
+        };
+    };
+
+    save_paths::resolve_locations(
+        &entry,
+        &ResolveContext {
+            steam_root: steam_libraries.root_for_app(database_id),
+            ..Default::default()
+        },
+    )
+    .into_iter()
+    .find(|location| location.path == save_location)
+    .unwrap_or_else(|| ResolvedLocation {
+        path: save_location.to_string(),
+        include: entry.save_pattern.clone(),
+        exclude: entry.exclude.clone(),
+    })
+}
+
+// Whether a configured save location (which may contain wildcards) points at existing data
+fn save_location_exists(save_location: &str) -> bool {
+    match safe_expand_tilde(save_location) {
+        Ok(path) if save_location.contains('*') => glob::glob(&path.to_string_lossy())
+            .map(|mut paths| paths.any(|p| p.is_ok()))
+            .unwrap_or(false),
+        Ok(path) => path.exists(),
+        Err(_) => false,
+    }
+}
+
+// Whether the game has saves to back up, natively or in its Proton prefix
+pub(crate) fn game_saves_present(game: &Game, steam_libraries: &SteamLibraries) -> bool {
+    !game_save_paths(game, steam_libraries).is_empty()
+}
+
+// Existing paths holding the game's saves, natively or in its Proton prefix
+pub(crate) fn game_save_paths(game: &Game, steam_libraries: &SteamLibraries) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if !game.save_location.is_empty() {
+        match safe_expand_tilde(&game.save_location) {
+            Ok(path) if game.save_location.contains('*') => {
+                if let Ok(matches) = glob::glob(&path.to_string_lossy()) {
+                    paths.extend(matches.filter_map(Result::ok));
+                }
+            }
+            Ok(path) if path.exists() => paths.push(path),
+            _ => {}
+        }
+    }
+    if let Some(location) = get_proton_save_location(game, steam_libraries) {
+        if let Ok(path) = safe_expand_tilde(&location.path) {
+            if path.exists() && !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+    }
+    paths
+}
+
+// On Linux, map the game's Windows save locations into its Proton prefix
+#[cfg(target_os = "linux")]
+fn get_proton_save_location(
+    game: &Game,
+    steam_libraries: &SteamLibraries,
+) -> Option<ResolvedLocation> {
+    let game_config = load_game_config().ok()?;
+    let steam_id = game.database_id.as_deref()?;
+    let game_entry = game_config.get(steam_id)?;
+
+    let roots = steam_libraries.library_roots();
+    save_paths::resolve_windows_locations(game_entry, &ResolveContext::default(), |path| {
+        crate::proton::resolve_save_location(steam_id, path, &roots)
+    })
+    .into_iter()
+    .next()
+    .inspect(|location| {
+        println!("Resolved save location in Proton prefix: {:?}", location.path);
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn get_proton_save_location(
+    _game: &Game,
+    _steam_libraries: &SteamLibraries,
+) -> Option<ResolvedLocation> {
+    None
+}
+
+// For save locations that glob across Steam accounts (userdata/*/...), pick one account:
+// the requested one, otherwise the most recently logged in account that has saves.
+// Returns None when the location is not stored per account.
+fn select_steam_account(
+    save_location: &str,
+    requested: Option<&str>,
+) -> Result<Option<(String, SteamAccount)>, SaveFileError> {
+    if game_scanner::save_location_for_account(save_location, "0").is_none() {
+        return Ok(None);
+    }
+
+    let accounts = game_scanner::steam_accounts();
+
+    if let Some(account_id) = requested {
+        validate_path_component(account_id)?;
+        let account = accounts
+            .into_iter()
+            .find(|account| account.account_id == account_id)
+            .ok_or_else(|| SaveFileError {
+                message: format!("Unknown Steam account: {}", account_id),
+            })?;
+        return Ok(game_scanner::save_location_for_account(save_location, account_id)
+            .map(|location| (location, account)));
+    }
+
+    let mut with_saves: Vec<(String, SteamAccount)> = accounts
+        .into_iter()
+        .filter_map(|account| {
+            let location =
+                game_scanner::save_location_for_account(save_location, &account.account_id)?;
+            save_location_exists(&location).then_some((location, account))
+        })
+        .collect();
+    with_saves.sort_by_key(|(_, account)| !account.most_recent);
+
+    Ok(with_saves.into_iter().next())
+}
+
+#[derive(Debug, Serialize)]
+pub struct SteamAccountSaves {
+    pub account: SteamAccount,
+    pub save_location: String,
+    pub has_saves: bool,
+}
+
+// List the Steam accounts on this machine and whether each has saves for the game.
+// Empty when the game's saves are not stored per Steam account.
+#[tauri::command]
+pub async fn get_steam_accounts(game_id: String) -> Result<Vec<SteamAccountSaves>, SaveFileError> {
+    validate_path_component(&game_id)?;
+
+    let game = get_game_by_id(game_id.clone()).await?;
+    let save_location = if !game.save_location.is_empty() {
+        game.save_location
+    } else {
+        get_save_location_from_config(&game, &SteamLibraries::load())?
+    };
+
+    if game_scanner::save_location_for_account(&save_location, "0").is_none() {
+        return Ok(Vec::new());
+    }
+
+    Ok(game_scanner::steam_accounts()
+        .into_iter()
+        .filter_map(|account| {
+            let location =
+                game_scanner::save_location_for_account(&save_location, &account.account_id)?;
+            Some(SteamAccountSaves {
+                has_saves: save_location_exists(&location),
+                save_location: location,
+                account,
+            })
+        })
+        .collect())
+}
+
+// Steam account recorded for a backup, if any
+async fn get_save_file_steam_account(
+    game_id: String,
+    save_id: String,
+) -> Result<Option<String>, SaveFileError> {
+    db::execute_blocking(move |conn| {
+        match conn.query_row(
+            "SELECT steam_account_id FROM save_files WHERE game_id = ?1 AND id = ?2",
+            rusqlite::params![game_id, save_id],
+            |row| row.get::<_, Option<String>>(0),
+        ) {
+            Ok(account_id) => Ok(account_id),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(format!("Failed to read save file account: {}", e)),
+        }
+    })
+    .await
+    .map_err(|e| SaveFileError { message: e })
+}
+
 // Database operations for save files
 async fn add_save_file_to_db(save_file: &SaveFile) -> Result<(), SaveFileError> {
     let save_file_clone = save_file.clone();
@@ -593,8 +1157,9 @@ async fn add_save_file_to_db(save_file: &SaveFile) -> Result<(), SaveFileError>
         conn.execute(
             "INSERT OR REPLACE INTO save_files (
                 id, game_id, file_name, created_at, modified_at, 
-                size_bytes, file_path, cloud
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                size_bytes, file_path, cloud, steam_account_id, steam_persona_name,
+                verification_status, verified_at, pinned, tags, label, notes
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
             rusqlite::params![
                 save_file_clone.id,
                 save_file_clone.game_id,
@@ -604,6 +1169,14 @@ async fn add_save_file_to_db(save_file: &SaveFile) -> Result<(), SaveFileError>
                 save_file_clone.size_bytes,
                 save_file_clone.file_path,
                 save_file_clone.cloud,
+                save_file_clone.steam_account_id,
+                save_file_clone.steam_persona_name,
+                save_file_clone.verification_status,
+                save_file_clone.verified_at,
+                save_file_clone.pinned,
+                format_patterns(&save_file_clone.tags),
+                save_file_clone.label,
+                save_file_clone.notes,
             ],
         )
         .map_err(|e| format!("Failed to add save file to database: {}", e))?;
@@ -613,20 +1186,26 @@ async fn add_save_file_to_db(save_file: &SaveFile) -> Result<(), SaveFileError>
     .map_err(|e| SaveFileError { message: e })
 }
 
-async fn get_save_files_from_db(game_id: String) -> Result<Vec<SaveFile>, SaveFileError> {
+// A game's backups, newest first; only those with `tag` when given
+async fn get_save_files_from_db(
+    game_id: String,
+    tag: Option<String>,
+) -> Result<Vec<SaveFile>, SaveFileError> {
     db::execute_blocking(move |conn| {
         let mut stmt = conn
             .prepare(
                 "SELECT id, game_id, file_name, created_at, modified_at, 
-                        size_bytes, file_path, cloud 
+                        size_bytes, file_path, cloud, steam_account_id, steam_persona_name,
+                        verification_status, verified_at, pinned, tags, label, notes
                  FROM save_files 
-                 WHERE game_id = ?1 
+                 WHERE game_id = ?1 AND (?2 IS NULL OR EXISTS (
+                     SELECT 1 FROM json_each(save_files.tags) WHERE lower(value) = lower(?2)))
                  ORDER BY created_at DESC"
             )
             .map_err(|e| format!("Failed to prepare statement: {}", e))?;
         
         let saves = stmt
-            .query_map(rusqlite::params![game_id], |row| {
+            .query_map(rusqlite::params![game_id, tag], |row| {
                 Ok(SaveFile {
                     id: row.get(0)?,
                     game_id: row.get(1)?,
@@ -634,10 +1213,17 @@ async fn get_save_files_from_db(game_id: String) -> Result<Vec<SaveFile>, SaveFi
                     created_at: row.get(3)?,
                     modified_at: row.get(4)?,
                     size_bytes: row.get(5)?,
-                    tags: Vec::new(),
+                    tags: parse_patterns(row.get(13)?).unwrap_or_default(),
                     file_path: row.get(6)?,
                     origin_path: String::new(), // Will be populated from game data
                     cloud: row.get(7)?,
+                    steam_account_id: row.get(8)?,
+                    steam_persona_name: row.get(9)?,
+                    verification_status: row.get(10)?,
+                    verified_at: row.get(11)?,
+                    pinned: row.get(12)?,
+                    label: row.get(14)?,
+                    notes: row.get(15)?,
                 })
             })
             .map_err(|e| format!("Failed to query save files: {}", e))?
@@ -728,9 +1314,63 @@ async fn _update_all_save_counts() -> Result<(), SaveFileError> {
     Ok(())
 }
 
+// Games being backed up right now. The scheduler, save watcher, process monitor and UI can all
+// ask for a backup, so each game is only backed up by one of them at a time.
+static BACKUPS_IN_PROGRESS: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+// Marks a game's backup as in progress until dropped
+pub(crate) struct BackupInProgress(String);
+
+impl BackupInProgress {
+    // None while another backup of the game is running
+    pub(crate) fn start(game_id: &str) -> Option<Self> {
+        let mut in_progress = BACKUPS_IN_PROGRESS.lock().ok()?;
+        in_progress
+            .insert(game_id.to_string())
+            .then(|| BackupInProgress(game_id.to_string()))
+    }
+}
+
+impl Drop for BackupInProgress {
+    fn drop(&mut self) {
+        if let Ok(mut in_progress) = BACKUPS_IN_PROGRESS.lock() {
+            in_progress.remove(&self.0);
+        }
+    }
+}
+
 #[tauri::command]
-pub async fn backup_save(game_id: String) -> Result<BackupResponse, SaveFileError> {
+pub async fn backup_save(
+    game_id: String,
+    steam_account_id: Option<String>,
+    force: Option<bool>,
+    label: Option<String>,
+    notes: Option<String>,
+    tags: Option<Vec<String>>,
+) -> Result<BackupResponse, SaveFileError> {
+    let Some(_in_progress) = BackupInProgress::start(&game_id) else {
+        return Err(SaveFileError {
+            message: format!("A backup of game {} is already in progress", game_id),
+        });
+    };
+    backup_game_saves(game_id, steam_account_id, force, label, notes, tags).await
+}
+
+// Back up a game's saves; callers hold the game's `BackupInProgress`
+pub(crate) async fn backup_game_saves(
+    game_id: String,
+    steam_account_id: Option<String>,
+    force: Option<bool>,
+    label: Option<String>,
+    notes: Option<String>,
+    tags: Option<Vec<String>>,
+) -> Result<BackupResponse, SaveFileError> {
     println!("=== Starting backup for game: {} ===", game_id);
+    let label = label
+        .map(|label| label.trim().to_string())
+        .filter(|label| !label.is_empty());
+    let notes = notes.filter(|notes| !notes.trim().is_empty());
+    let tags = clean_tags(tags.unwrap_or_default());
 
     // Load backup settings
     let settings = load_backup_settings().await?;
@@ -746,6 +1386,7 @@ pub async fn backup_save(game_id: String) -> Result<BackupResponse, SaveFileErro
     // Get the game from database to get the actual save location
     let game = get_game_by_id(game_id.clone()).await?;
     println!("Game info: {:?}", game);
+    let steam_libraries = SteamLibraries::load();
 
     // Get the origin path from the game's save_location
     let save_location = if !game.save_location.is_empty() {
@@ -757,7 +1398,7 @@ pub async fn backup_save(game_id: String) -> Result<BackupResponse, SaveFileErro
             "No save location in database for game {}, checking JSON config...",
             game_id
         );
-        match get_save_location_from_config(&game_id) {
+        match get_save_location_from_config(&game, &steam_libraries) {
             Ok(location) => {
                 println!("Found save location in config: {}", location);
                 // Update the database with this location for future use
@@ -786,6 +1427,29 @@ pub async fn backup_save(game_id: String) -> Result<BackupResponse, SaveFileErro
         }
     };
 
+    // Native location is empty; the game may be running through Proton
+    let save_location = if save_location_exists(&save_location) {
+        save_location
+    } else {
+        get_proton_save_location(&game, &steam_libraries)
+            .map(|location| location.path)
+            .unwrap_or(save_location)
+    };
+    let filters = get_save_location_filters(&game, &save_location, &steam_libraries);
+
+    // Saves under userdata/*/ belong to individual Steam accounts; back up a single account
+    let steam_account = select_steam_account(&save_location, steam_account_id.as_deref())?;
+    let save_location = match &steam_account {
+        Some((location, account)) => {
+            println!(
+                "Backing up saves of Steam account {} ({})",
+                account.account_id, account.persona_name
+            );
+            location.clone()
+        }
+        None => save_location,
+    };
+
     // Check if save_location contains wildcard
     let (has_wildcard, pattern_info) = if save_location.contains("*") {
         // For wildcard patterns, we need special handling
@@ -799,12 +1463,13 @@ pub async fn backup_save(game_id: String) -> Result<BackupResponse, SaveFileErro
     println!("Save location: {}", save_location);
     println!("Expanded save location: {:?}", origin_path);
 
-    // Create a new backup with timestamp
-    let timestamp = Local::now().format("%Y%m%d_%H%M%S").to_string();
+    // Create a new backup with timestamp, down to the millisecond so backups never share a name
+    let timestamp = Local::now().format("%Y%m%d_%H%M%S_%3f").to_string();
     let backup_name = format!("backup_{}", timestamp);
-    let backup_path = game_saves_dir.join(&backup_name);
+    let backup_path = backup_store::manifest_path(&game_saves_dir, &backup_name);
 
-    let mut total_size = 0u64;
+    // Files to back up, by path relative to the backup
+    let mut files_to_store: Vec<(String, PathBuf)> = Vec::new();
     let mut found_save = false;
 
     println!("Checking for save files in: {:?}", origin_path);
@@ -827,185 +1492,372 @@ pub async fn backup_save(game_id: String) -> Result<BackupResponse, SaveFileErro
                 }
             }
 
-            if !files_to_backup.is_empty() {
-                // Create backup directory
-                create_dir_all(&backup_path).map_err(|e| SaveFileError {
-                    message: format!("Failed to create backup directory: {}", e),
-                })?;
-
-                // Copy all matching files/directories
-                for path in files_to_backup {
-                    if path.is_dir() {
-                        let dir_name = path.file_name().unwrap_or_default();
-                        let target_dir = backup_path.join(dir_name);
-                        match copy_dir_recursive(&path, &target_dir) {
-                            Ok(size) => {
-                                total_size += size;
-                                found_save = true;
-                            }
-                            Err(e) => {
-                                println!("Failed to backup directory {:?}: {}", path, e);
-                            }
-                        }
-                    } else if path.is_file() {
-                        let file_name = path.file_name().unwrap_or_default();
-                        let target_file = backup_path.join(file_name);
-
-                        if let Ok(metadata) = fs::metadata(&path) {
-                            total_size += metadata.len();
-                        }
-
-                        if let Err(e) = fs::copy(&path, &target_file) {
-                            println!("Failed to copy file {:?}: {}", path, e);
-                        } else {
-                            found_save = true;
-                        }
-                    }
+            // Each match is kept under its own name
+            for path in files_to_backup {
+                let name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+                if path.is_dir() {
+                    collect_save_files(&path, &name, &filters, &mut files_to_store);
+                    found_save = true;
+                } else if path.is_file() {
+                    files_to_store.push((name, path));
+                    found_save = true;
                 }
-
-                println!("Backed up {} bytes from wildcard pattern", total_size);
             }
         }
     } else if origin_path.exists() {
         println!("Directory exists, creating backup...");
 
-        // For pattern "*", backup the entire directory
         if origin_path.is_dir() {
-            println!(
-                "Backing up entire directory: {:?} to {:?}",
-                origin_path, backup_path
-            );
-
-            match copy_dir_recursive(&origin_path, &backup_path) {
-                Ok(size) => {
-                    total_size = size;
-                    found_save = true;
-                    println!(
-                        "Successfully backed up directory, total size: {} bytes",
-                        total_size
-                    );
-                }
-                Err(e) => {
-                    return Err(SaveFileError {
-                        message: format!("Failed to backup directory: {}", e),
-                    });
-                }
-            }
+            println!("Backing up directory: {:?}", origin_path);
+            collect_save_files(&origin_path, "", &filters, &mut files_to_store);
+            found_save = true;
         } else if origin_path.is_file() {
             // If it's a single file, just copy it
             println!("Backing up single file: {:?}", origin_path);
-            let file_name = origin_path.file_name().unwrap_or_default();
-            let save_path = backup_path.join(file_name);
+            let name = origin_path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned();
+            files_to_store.push((name, origin_path.clone()));
+            found_save = true;
+        }
+    } else {
+        println!("Save location does not exist: {:?}", origin_path);
+    }
 
-            create_dir_all(&backup_path).map_err(|e| SaveFileError {
-                message: format!("Failed to create backup directory: {}", e),
-            })?;
+    if !found_save {
+        let error_msg = format!("No save data found at: {:?}", origin_path);
+        println!("{}", error_msg);
+        return Err(SaveFileError { message: error_msg });
+    }
 
-            if let Ok(metadata) = fs::metadata(&origin_path) {
-                total_size = metadata.len();
+    // Unchanged saves would only push distinct backups out of the rotation
+    if !force.unwrap_or(false) {
+        let account_id = steam_account
+            .as_ref()
+            .map(|(_, account)| account.account_id.clone());
+        if let Some(mut latest) =
+            find_unchanged_backup(&game_saves_dir, &game_id, account_id, &files_to_store).await?
+        {
+            println!(
+                "Saves unchanged since backup {}, skipping new backup",
+                latest.id
+            );
+            // Metadata given for the skipped backup goes to the latest one instead
+            if label.is_some() || notes.is_some() || !tags.is_empty() {
+                let tags = clean_tags(latest.tags.iter().cloned().chain(tags));
+                latest = update_save_metadata(
+                    game_id.clone(),
+                    latest.id.clone(),
+                    latest.label.clone().or(label),
+                    latest.notes.clone().or(notes),
+                    tags,
+                )
+                .await?;
             }
+            let backup_time = Utc::now().timestamp_millis();
+            set_last_backup_time(game_id.clone(), backup_time).await?;
+            latest.origin_path = origin_path.to_string_lossy().into_owned();
+            return Ok(BackupResponse {
+                save_file: latest,
+                backup_time,
+                save_count: count_save_files(game_id).await,
+                created: false,
+            });
+        }
+    }
 
-            if let Err(e) = fs::copy(&origin_path, &save_path) {
-                return Err(SaveFileError {
-                    message: format!("Failed to copy save file: {}", e),
-                });
-            }
-            found_save = true;
+    // Store the files once in the backup store and record the backup as a manifest
+    let manifest = backup_store::create_backup(
+        &saves_dir,
+        &backup_path,
+        &game_id,
+        &files_to_store,
+        settings.compression(),
+    )
+    .map_err(|e| SaveFileError {
+        message: format!("Failed to store backup: {}", e),
+    })?;
+    let total_size = manifest.total_size();
+    println!(
+        "Backed up {} files ({} bytes) to {:?}",
+        manifest.files.len(),
+        total_size,
+        backup_path
+    );
+
+    let backup_time = Utc::now().timestamp_millis();
+
+    // Update last_backup_time
+    set_last_backup_time(game_id.clone(), backup_time).await?;
+
+    // Create save file record
+    let mut save_file = SaveFile::new(
+        game_id.clone(),
+        backup_name.clone(),
+        total_size,
+        backup_path.to_string_lossy().into_owned(),
+        origin_path.to_string_lossy().into_owned(),
+    );
+    if let Some((_, account)) = steam_account {
+        save_file.steam_account_id = Some(account.account_id);
+        save_file.steam_persona_name =
+            Some(account.persona_name).filter(|name| !name.is_empty());
+    }
+    
+    save_file.label = label;
+    save_file.notes = notes;
+    save_file.tags = tags;
+
+    // Add save file to database
+    add_save_file_to_db(&save_file).await?;
+
+    // Prune old backups; this also updates the game's save_count
+    let policy = game
+        .retention_policy
+        .clone()
+        .unwrap_or_else(|| settings.retention_policy());
+    apply_retention(&game_id, &policy, false).await?;
+    
+    // Get actual save count from database
+    let save_count = count_save_files(game_id).await;
+    
+    Ok(BackupResponse {
+        save_file,
+        backup_time,
+        save_count,
+        created: true,
+    })
+}
+
+// Backups kept and removed by a retention policy
+#[derive(Debug, Serialize)]
+pub struct RetentionReport {
+    pub kept: Vec<String>,
+    pub deleted: Vec<String>,
+}
+
+// Remove a game's recorded backups the policy doesn't keep; with `dry_run` only report them
+// Community saves aren't backups
+fn is_backup(save: &SaveFile) -> bool {
+    save.cloud.as_deref() != Some("community")
+}
+
+// Backups the retention policy drops. Saves kept per Steam account are rotated per account, so
+// backups of one account never push out those of another.
+fn expired_backups(saves: &[SaveFile], policy: &RetentionPolicy) -> Vec<String> {
+    let mut by_account: HashMap<Option<&str>, Vec<BackupRecord>> = HashMap::new();
+    for save in saves.iter().filter(|save| is_backup(save)) {
+        by_account
+            .entry(save.steam_account_id.as_deref())
+            .or_default()
+            .push(BackupRecord::new(&save.id, &save.created_at, save.pinned));
+    }
+    by_account
+        .values()
+        .flat_map(|backups| retention::expired_backups(backups, policy))
+        .collect()
+}
+
+async fn apply_retention(
+    game_id: &str,
+    policy: &RetentionPolicy,
+    dry_run: bool,
+) -> Result<RetentionReport, SaveFileError> {
+    let saves = get_save_files_from_db(game_id.to_string(), None).await?;
+    let expired = expired_backups(&saves, policy);
+    let kept = saves
+        .iter()
+        .filter(|save| is_backup(save) && !expired.contains(&save.id))
+        .map(|save| save.id.clone())
+        .collect();
+    if dry_run {
+        return Ok(RetentionReport {
+            kept,
+            deleted: expired,
+        });
+    }
+
+    let saves_dir = get_saves_directory()?;
+    let game_saves_dir = safe_join_path(&saves_dir, game_id)?;
+    let mut deleted = Vec::new();
+    for save_id in expired {
+        println!("Removing old backup: {}", save_id);
+        if let Err(e) =
+            validate_path_component(&save_id).and_then(|_| remove_backup(&game_saves_dir, &save_id))
+        {
+            println!("Failed to remove old backup: {}", e.message);
+            continue;
+        }
+        if let Err(e) = delete_save_file_from_db(game_id.to_string(), save_id.clone()).await {
+            println!("Failed to remove old backup from database: {}", e.message);
+        }
+        deleted.push(save_id);
+    }
+    if !deleted.is_empty() {
+        if let Err(e) = backup_store::collect_garbage(&saves_dir) {
+            println!("Failed to collect backup store garbage: {}", e);
         }
-    } else {
-        println!("Save location does not exist: {:?}", origin_path);
     }
 
-    if !found_save {
-        let error_msg = format!("No save data found at: {:?}", origin_path);
-        println!("{}", error_msg);
-        return Err(SaveFileError { message: error_msg });
-    }
+    // Update the game's save_count based on database records
+    update_game_save_count(game_id.to_string()).await?;
+    Ok(RetentionReport { kept, deleted })
+}
+
+// Apply a retention policy to a game's backups: the given one, else the game's own, else the
+// backup settings'. With `dry_run` nothing is deleted and the backups that would be are listed.
+#[tauri::command]
+pub async fn prune_backups(
+    game_id: String,
+    policy: Option<RetentionPolicy>,
+    dry_run: Option<bool>,
+) -> Result<RetentionReport, SaveFileError> {
+    validate_path_component(&game_id)?;
+    let policy = match policy {
+        Some(policy) => policy,
+        None => match get_game_by_id(game_id.clone()).await?.retention_policy {
+            Some(policy) => policy,
+            None => load_backup_settings().await?.retention_policy(),
+        },
+    };
+    let dry_run = dry_run.unwrap_or(false);
+    let report = apply_retention(&game_id, &policy, dry_run).await?;
+    let action = if dry_run { "would delete" } else { "deleted" };
+    println!(
+        "Retention for {}: keeping {}, {} {}",
+        game_id,
+        report.kept.len(),
+        action,
+        report.deleted.len()
+    );
+    Ok(report)
+}
+
+// Pin a backup so retention never removes it, or unpin it
+#[tauri::command]
+pub async fn set_save_pinned(
+    game_id: String,
+    save_id: String,
+    pinned: bool,
+) -> Result<(), SaveFileError> {
+    db::execute_blocking(move |conn| {
+        let updated = conn
+            .execute(
+                "UPDATE save_files SET pinned = ?1 WHERE game_id = ?2 AND id = ?3",
+                rusqlite::params![pinned, game_id, save_id],
+            )
+            .map_err(|e| format!("Failed to update pinned state: {}", e))?;
+        if updated == 0 {
+            return Err(format!("Save file not found: {}", save_id));
+        }
+        Ok(())
+    })
+    .await
+    .map_err(|e| SaveFileError { message: e })
+}
+
+// Set the label, notes and tags of a backup, replacing the previous ones
+#[tauri::command]
+pub async fn update_save_metadata(
+    game_id: String,
+    save_id: String,
+    label: Option<String>,
+    notes: Option<String>,
+    tags: Vec<String>,
+) -> Result<SaveFile, SaveFileError> {
+    let label = label
+        .map(|label| label.trim().to_string())
+        .filter(|label| !label.is_empty());
+    let notes = notes.filter(|notes| !notes.trim().is_empty());
+    let tags = clean_tags(tags);
 
-    let backup_time = Utc::now().timestamp_millis();
+    let (db_game_id, db_save_id) = (game_id.clone(), save_id.clone());
+    db::execute_blocking(move |conn| {
+        let updated = conn
+            .execute(
+                "UPDATE save_files SET label = ?1, notes = ?2, tags = ?3 WHERE game_id = ?4 AND id = ?5",
+                rusqlite::params![label, notes, format_patterns(&tags), db_game_id, db_save_id],
+            )
+            .map_err(|e| format!("Failed to update save metadata: {}", e))?;
+        if updated == 0 {
+            return Err(format!("Save file not found: {}", db_save_id));
+        }
+        Ok(())
+    })
+    .await
+    .map_err(|e| SaveFileError { message: e })?;
 
-    // Count total number of backups (directories starting with "backup_")
-    let mut backup_entries: Vec<_> = fs::read_dir(&game_saves_dir)
-        .map_err(|e| SaveFileError {
-            message: format!("Failed to read saves directory: {}", e),
-        })?
-        .filter_map(Result::ok)
-        .filter(|entry| {
-            let name = entry.file_name().to_string_lossy().to_string();
-            name.starts_with("backup_") && entry.file_type().map(|ft| ft.is_dir()).unwrap_or(false)
+    get_save_files_from_db(game_id, None)
+        .await?
+        .into_iter()
+        .find(|save| save.id == save_id)
+        .ok_or_else(|| SaveFileError {
+            message: format!("Save file not found: {}", save_id),
         })
-        .collect();
+}
 
-    // Sort backups by creation time (newest first)
-    backup_entries.sort_by(|a, b| {
-        b.metadata()
-            .and_then(|m| m.created())
-            .unwrap_or_else(|_| std::time::SystemTime::UNIX_EPOCH)
-            .cmp(
-                &a.metadata()
-                    .and_then(|m| m.created())
-                    .unwrap_or_else(|_| std::time::SystemTime::UNIX_EPOCH),
-            )
-    });
+// The latest backup of the Steam account (or of the game, for saves not stored per account),
+// if it holds exactly the files about to be backed up
+async fn find_unchanged_backup(
+    game_saves_dir: &Path,
+    game_id: &str,
+    steam_account_id: Option<String>,
+    files: &[(String, PathBuf)],
+) -> Result<Option<SaveFile>, SaveFileError> {
+    let latest = get_save_files_from_db(game_id.to_string(), None)
+        .await?
+        .into_iter()
+        .find(|save| {
+            save.cloud.as_deref() != Some("community") && save.steam_account_id == steam_account_id
+        });
+    let Some(latest) = latest else {
+        return Ok(None);
+    };
 
-    // Remove old backups if we exceed max_backups
-    if backup_entries.len() > settings.max_backups as usize {
-        for old_backup in backup_entries.iter().skip(settings.max_backups as usize) {
-            let backup_path = old_backup.path();
-            if backup_path.is_dir() {
-                if let Err(e) = fs::remove_dir_all(&backup_path) {
-                    println!("Failed to remove old backup directory: {}", e);
-                }
-            }
+    // Backups made before manifests have no fingerprint to compare against
+    let manifest_path = backup_store::manifest_path(game_saves_dir, &latest.id);
+    if !manifest_path.exists() {
+        return Ok(None);
+    }
+    let unchanged = backup_store::read_manifest(&manifest_path)
+        .and_then(|manifest| backup_store::matches_manifest(&manifest, files));
+    match unchanged {
+        Ok(true) => Ok(Some(latest)),
+        Ok(false) => Ok(None),
+        Err(e) => {
+            println!("Failed to compare saves with backup {}: {}", latest.id, e);
+            Ok(None)
         }
     }
+}
 
-    // Update the game's save_count based on database records
-    update_game_save_count(game_id.clone()).await?;
-    
-    // Update last_backup_time
-    let game_id_for_time = game_id.clone();
+async fn set_last_backup_time(game_id: String, backup_time: i64) -> Result<(), SaveFileError> {
     db::execute_blocking(move |conn| {
         conn.execute(
             "UPDATE games SET last_backup_time = ?1 WHERE id = ?2",
-            params![backup_time, game_id_for_time],
+            params![backup_time, game_id],
         )
         .map_err(|e| format!("Failed to update last backup time: {}", e))?;
         Ok(())
     })
     .await
-    .map_err(|e| SaveFileError { message: e })?;
+    .map_err(|e| SaveFileError { message: e })
+}
 
-    // Create save file record
-    let save_file = SaveFile::new(
-        game_id.clone(),
-        backup_name.clone(),
-        total_size,
-        backup_path.to_string_lossy().into_owned(),
-        origin_path.to_string_lossy().into_owned(),
-    );
-    
-    // Add save file to database
-    add_save_file_to_db(&save_file).await?;
-    
-    // Get actual save count from database
-    let game_id_for_count = game_id.clone();
-    let save_count = db::execute_blocking(move |conn| {
-        Ok(conn.query_row(
-            "SELECT COUNT(*) FROM save_files WHERE game_id = ?1",
-            rusqlite::params![game_id_for_count],
-            |row| row.get::<_, i32>(0),
-        )
-        .unwrap_or(0))
+async fn count_save_files(game_id: String) -> i32 {
+    db::execute_blocking(move |conn| {
+        Ok(conn
+            .query_row(
+                "SELECT COUNT(*) FROM save_files WHERE game_id = ?1",
+                rusqlite::params![game_id],
+                |row| row.get::<_, i32>(0),
+            )
+            .unwrap_or(0))
     })
     .await
-    .unwrap_or(0);
-    
-    Ok(BackupResponse {
-        save_file,
-        backup_time,
-        save_count,
-    })
+    .unwrap_or(0)
 }
 
 // Sync existing file system backups to database
@@ -1033,7 +1885,7 @@ async fn sync_existing_backups_to_db(game_id: String) -> Result<i32, SaveFileErr
     let mut synced_count = 0;
     
     // Get existing save IDs from database
-    let existing_saves = get_save_files_from_db(game_id.clone()).await?;
+    let existing_saves = get_save_files_from_db(game_id.clone(), None).await?;
     let db_save_ids: Vec<String> = existing_saves.iter().map(|s| s.id.clone()).collect();
     
     // Read all backup directories from file system
@@ -1111,9 +1963,12 @@ async fn _sync_all_existing_backups() -> Result<i32, SaveFileError> {
 }
 
 #[tauri::command]
-pub async fn list_saves(game_id: String) -> Result<Vec<SaveFile>, SaveFileError> {
+pub async fn list_saves(
+    game_id: String,
+    tag: Option<String>,
+) -> Result<Vec<SaveFile>, SaveFileError> {
     // Get saves from database only
-    let mut saves = get_save_files_from_db(game_id.clone()).await?;
+    let mut saves = get_save_files_from_db(game_id.clone(), tag).await?;
     
     // Get the game to populate origin_path
     let game = get_game_by_id(game_id.clone()).await?;
@@ -1135,19 +1990,22 @@ pub async fn list_saves(game_id: String) -> Result<Vec<SaveFile>, SaveFileError>
 }
 
 #[tauri::command]
-pub async fn get_all_save_files() -> Result<Vec<SaveFile>, SaveFileError> {
+pub async fn get_all_save_files(tag: Option<String>) -> Result<Vec<SaveFile>, SaveFileError> {
     db::execute_blocking(move |conn| {
         let mut stmt = conn
             .prepare(
                 "SELECT id, game_id, file_name, created_at, modified_at, 
-                        size_bytes, file_path, cloud 
+                        size_bytes, file_path, cloud, steam_account_id, steam_persona_name,
+                        verification_status, verified_at, pinned, tags, label, notes
                  FROM save_files 
+                 WHERE ?1 IS NULL OR EXISTS (
+                     SELECT 1 FROM json_each(save_files.tags) WHERE lower(value) = lower(?1))
                  ORDER BY created_at DESC"
             )
             .map_err(|e| format!("Failed to prepare statement: {}", e))?;
         
         let saves = stmt
-            .query_map([], |row| {
+            .query_map(rusqlite::params![tag], |row| {
                 Ok(SaveFile {
                     id: row.get(0)?,
                     game_id: row.get(1)?,
@@ -1155,10 +2013,17 @@ pub async fn get_all_save_files() -> Result<Vec<SaveFile>, SaveFileError> {
                     created_at: row.get(3)?,
                     modified_at: row.get(4)?,
                     size_bytes: row.get(5)?,
-                    tags: Vec::new(),
+                    tags: parse_patterns(row.get(13)?).unwrap_or_default(),
                     file_path: row.get(6)?,
                     origin_path: String::new(),
                     cloud: row.get(7)?,
+                    steam_account_id: row.get(8)?,
+                    steam_persona_name: row.get(9)?,
+                    verification_status: row.get(10)?,
+                    verified_at: row.get(11)?,
+                    pinned: row.get(12)?,
+                    label: row.get(14)?,
+                    notes: row.get(15)?,
                 })
             })
             .map_err(|e| format!("Failed to query save files: {}", e))?
@@ -1181,23 +2046,12 @@ pub async fn delete_save(game_id: String, save_id: String) -> Result<(), SaveFil
     validate_path_component(&game_id)?;
     validate_path_component(&save_id)?;
     
-    // Get the backup file path
+    // Delete from file system, then drop the file contents no other backup uses
     let saves_dir = get_saves_directory()?;
     let game_saves_dir = safe_join_path(&saves_dir, &game_id)?;
-    let save_path = safe_join_path(&game_saves_dir, &save_id)?;
-    
-    // Delete from file system
-    if save_path.exists() {
-        if save_path.is_dir() {
-            fs::remove_dir_all(&save_path).map_err(|e| SaveFileError {
-                message: format!("Failed to delete save directory: {}", e),
-            })?;
-        } else {
-            fs::remove_file(&save_path).map_err(|e| SaveFileError {
-                message: format!("Failed to delete save file: {}", e),
-            })?;
-        }
-        println!("Deleted save file from disk: {:?}", save_path);
+    remove_backup(&game_saves_dir, &save_id)?;
+    if let Err(e) = backup_store::collect_garbage(&saves_dir) {
+        println!("Failed to collect backup store garbage: {}", e);
     }
     
     // Delete from database
@@ -1209,6 +2063,216 @@ pub async fn delete_save(game_id: String, save_id: String) -> Result<(), SaveFil
     Ok(())
 }
 
+// Remove backed up file contents no backup references any more
+#[tauri::command]
+pub async fn collect_backup_garbage() -> Result<backup_store::GarbageReport, SaveFileError> {
+    let saves_dir = get_saves_directory()?;
+    tokio::task::spawn_blocking(move || backup_store::collect_garbage(&saves_dir))
+        .await
+        .map_err(|e| SaveFileError {
+            message: format!("Task join error: {}", e),
+        })?
+        .map_err(|e| SaveFileError {
+            message: format!("Failed to collect backup store garbage: {}", e),
+        })
+}
+
+// Outcome of checking one backup against its manifest
+#[derive(Debug, Serialize, Clone)]
+pub struct BackupVerification {
+    pub game_id: String,
+    pub save_id: String,
+    // "ok", "missing", "corrupt", or "unverifiable" for backups made before manifests
+    pub status: String,
+    pub checked_at: String,
+    pub checked_files: usize,
+    pub missing_files: Vec<String>,
+    pub corrupt_files: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VerificationReport {
+    pub backups: Vec<BackupVerification>,
+    // Backups on disk that no save file record points to, as "game_id/save_id"
+    pub extra_backups: Vec<String>,
+}
+
+fn verify_backup_files(saves_dir: &Path, game_id: &str, save_id: &str) -> BackupVerification {
+    let mut verification = BackupVerification {
+        game_id: game_id.to_string(),
+        save_id: save_id.to_string(),
+        status: "ok".to_string(),
+        checked_at: Utc::now().to_rfc3339(),
+        checked_files: 0,
+        missing_files: Vec::new(),
+        corrupt_files: Vec::new(),
+    };
+
+    let game_saves_dir = saves_dir.join(game_id);
+    let manifest_path = backup_store::manifest_path(&game_saves_dir, save_id);
+    if !manifest_path.exists() {
+        verification.status = if game_saves_dir.join(save_id).exists() {
+            "unverifiable".to_string()
+        } else {
+            "missing".to_string()
+        };
+        return verification;
+    }
+
+    let report = backup_store::read_manifest(&manifest_path)
+        .and_then(|manifest| backup_store::verify_backup(saves_dir, &manifest));
+    match report {
+        Ok(report) => {
+            verification.checked_files = report.checked_files;
+            verification.missing_files = report.missing;
+            verification.corrupt_files = report.corrupt;
+            if !verification.corrupt_files.is_empty() {
+                verification.status = "corrupt".to_string();
+            } else if !verification.missing_files.is_empty() {
+                verification.status = "missing".to_string();
+            }
+        }
+        Err(e) => {
+            println!("Failed to verify backup {}/{}: {}", game_id, save_id, e);
+            verification.status = "corrupt".to_string();
+        }
+    }
+    verification
+}
+
+async fn record_verification(verification: &BackupVerification) -> Result<(), SaveFileError> {
+    let verification = verification.clone();
+    db::execute_blocking(move |conn| {
+        conn.execute(
+            "UPDATE save_files SET verification_status = ?1, verified_at = ?2
+             WHERE game_id = ?3 AND id = ?4",
+            rusqlite::params![
+                verification.status,
+                verification.checked_at,
+                verification.game_id,
+                verification.save_id
+            ],
+        )
+        .map_err(|e| format!("Failed to record backup verification: {}", e))?;
+        Ok(())
+    })
+    .await
+    .map_err(|e| SaveFileError { message: e })
+}
+
+// Re-hash a backup's files and report the ones that are missing or corrupt
+#[tauri::command]
+pub async fn verify_backup(
+    game_id: String,
+    save_id: String,
+) -> Result<BackupVerification, SaveFileError> {
+    validate_path_component(&game_id)?;
+    validate_path_component(&save_id)?;
+    let saves_dir = get_saves_directory()?;
+
+    let verification =
+        tokio::task::spawn_blocking(move || verify_backup_files(&saves_dir, &game_id, &save_id))
+            .await
+            .map_err(|e| SaveFileError {
+                message: format!("Task join error: {}", e),
+            })?;
+    println!(
+        "Verified backup {}/{}: {}",
+        verification.game_id, verification.save_id, verification.status
+    );
+    record_verification(&verification).await?;
+    Ok(verification)
+}
+
+// Verify every recorded backup, or those of one game, and list backups without a record
+#[tauri::command]
+pub async fn verify_all_backups(
+    game_id: Option<String>,
+) -> Result<VerificationReport, SaveFileError> {
+    if let Some(game_id) = &game_id {
+        validate_path_component(game_id)?;
+    }
+    let saves_dir = get_saves_directory()?;
+
+    // Community saves live outside the backup directory
+    let filter_game_id = game_id.clone();
+    let recorded: Vec<(String, String)> = db::execute_blocking(move |conn| {
+        let mut stmt = conn
+            .prepare(
+                "SELECT game_id, id FROM save_files
+                 WHERE (?1 IS NULL OR game_id = ?1) AND (cloud IS NULL OR cloud != 'community')
+                 ORDER BY game_id, created_at",
+            )
+            .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+        let rows = stmt
+            .query_map(rusqlite::params![filter_game_id], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .map_err(|e| format!("Failed to query save files: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to collect save files: {}", e))?;
+        Ok(rows)
+    })
+    .await
+    .map_err(|e| SaveFileError { message: e })?;
+
+    let (backups, extra_backups) = tokio::task::spawn_blocking(move || {
+        let backups: Vec<BackupVerification> = recorded
+            .iter()
+            .filter(|(game_id, save_id)| {
+                validate_path_component(game_id).is_ok() && validate_path_component(save_id).is_ok()
+            })
+            .map(|(game_id, save_id)| verify_backup_files(&saves_dir, game_id, save_id))
+            .collect();
+
+        let game_dirs: Vec<String> = match &game_id {
+            Some(game_id) => vec![game_id.clone()],
+            None => fs::read_dir(&saves_dir)
+                .map(|entries| {
+                    entries
+                        .filter_map(Result::ok)
+                        .filter(|entry| entry.path().is_dir())
+                        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                        .filter(|name| !name.starts_with('.'))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        };
+        let mut extra_backups = Vec::new();
+        for dir in game_dirs {
+            let Ok(names) = list_backup_names(&saves_dir.join(&dir)) else {
+                continue;
+            };
+            for name in names {
+                if !recorded.contains(&(dir.clone(), name.clone())) {
+                    extra_backups.push(format!("{}/{}", dir, name));
+                }
+            }
+        }
+        extra_backups.sort();
+        (backups, extra_backups)
+    })
+    .await
+    .map_err(|e| SaveFileError {
+        message: format!("Task join error: {}", e),
+    })?;
+
+    for verification in &backups {
+        record_verification(verification).await?;
+    }
+    println!(
+        "Verified {} backups, {} failed, {} without a record",
+        backups.len(),
+        backups.iter().filter(|backup| backup.status != "ok").count(),
+        extra_backups.len()
+    );
+
+    Ok(VerificationReport {
+        backups,
+        extra_backups,
+    })
+}
+
 #[tauri::command]
 pub async fn save_backup_settings(settings: BackupSettings) -> Result<(), SaveFileError> {
     let config_dir = dirs::config_local_dir()
@@ -1256,7 +2320,7 @@ pub async fn load_backup_settings() -> Result<BackupSettings, SaveFileError> {
     })
 }
 
-fn get_saves_directory() -> Result<PathBuf, SaveFileError> {
+pub(crate) fn get_saves_directory() -> Result<PathBuf, SaveFileError> {
     let app_data_dir = dirs::data_local_dir().ok_or_else(|| SaveFileError {
         message: "Failed to get app data directory".to_string(),
     })?;
@@ -1267,7 +2331,10 @@ fn get_saves_directory() -> Result<PathBuf, SaveFileError> {
 // Sync scanned game to database
 #[tauri::command]
 pub async fn add_game_to_library(game_info: serde_json::Value) -> Result<(), SaveFileError> {
-    let game_id = game_info["id"].as_str().unwrap_or("").to_string();
+    let scanned_id = game_info["id"].as_str().unwrap_or("").to_string();
+    // Store ids of the detection; games already in the library under one of them are updated
+    let store_ids: Vec<StoreId> =
+        serde_json::from_value(game_info["store_ids"].clone()).unwrap_or_default();
     let title = game_info["title"].as_str().unwrap_or("").to_string();
     let cover_image = game_info["cover_image"].as_str().unwrap_or("").to_string();
     let platform = game_info["platform"].as_str().unwrap_or("").to_string();
@@ -1286,6 +2353,21 @@ pub async fn add_game_to_library(game_info: serde_json::Value) -> Result<(), Sav
         .unwrap_or("Unknown")
         .to_string();
     let is_favorite = game_info["is_favorite"].as_bool().unwrap_or(false);
+    // Save rules, only given for manually added games; scans keep the stored ones
+    let save_pattern = patterns_from_value(&game_info["save_pattern"])
+        .as_deref()
+        .and_then(format_patterns);
+    let exclude_patterns = patterns_from_value(&game_info["exclude_patterns"])
+        .as_deref()
+        .and_then(format_patterns);
+    let install_path = game_info["install_path"]
+        .as_str()
+        .filter(|path| !path.is_empty())
+        .map(str::to_string);
+    let database_id = game_info["database_id"]
+        .as_str()
+        .filter(|id| !id.is_empty())
+        .map(str::to_string);
 
     // Get save location - handle both array format (from scanner) and string format (from manual add)
     let save_location = if let Some(location_str) = game_info["save_location"].as_str() {
@@ -1309,22 +2391,51 @@ pub async fn add_game_to_library(game_info: serde_json::Value) -> Result<(), Sav
     };
 
     db::execute_blocking(move |conn| {
-        // Check if game exists
-        let exists: bool = conn
-            .query_row(
+        // Check if game exists, under its own id, one of its store ids or its database entry
+        let exists_with_id = |id: &str| -> bool {
+            conn.query_row(
                 "SELECT EXISTS(SELECT 1 FROM games WHERE id = ?1)",
-                params![&game_id],
+                params![id],
+                |row| row.get(0),
+            )
+            .unwrap_or(false)
+        };
+        let database_game_id = || -> Option<String> {
+            conn.query_row(
+                "SELECT id FROM games WHERE database_id = ?1",
+                params![database_id.as_deref()?],
                 |row| row.get(0),
             )
-            .unwrap_or(false);
+            .ok()
+        };
+        let game_id = if exists_with_id(&scanned_id) {
+            scanned_id
+        } else {
+            match game_identity::find_game_id(conn, &store_ids)?
+                .filter(|linked_id| exists_with_id(linked_id))
+                .or_else(database_game_id)
+            {
+                Some(linked_id) => {
+                    println!("Game {} is already in the library as {}", scanned_id, linked_id);
+                    linked_id
+                }
+                None => scanned_id,
+            }
+        };
+        let exists = exists_with_id(&game_id);
 
         if exists {
             // Update existing game
             conn.execute(
                 "UPDATE games SET 
-                    title = ?2, cover_image = ?3, platform = ?4, last_played = ?5,
+                    title = ?2, cover_image = ?3, platform = ?4,
+                    last_played = CASE WHEN last_session_end IS NULL THEN ?5 ELSE last_played END,
                     save_count = ?6, size = ?7, status = ?8, category = ?9,
-                    is_favorite = ?10, save_location = ?11
+                    is_favorite = ?10, save_location = ?11,
+                    save_pattern = COALESCE(?12, save_pattern),
+                    exclude_patterns = COALESCE(?13, exclude_patterns),
+                    install_path = COALESCE(?14, install_path),
+                    database_id = COALESCE(?15, database_id)
                 WHERE id = ?1",
                 params![
                     game_id,
@@ -1337,7 +2448,11 @@ pub async fn add_game_to_library(game_info: serde_json::Value) -> Result<(), Sav
                     status,
                     category,
                     is_favorite,
-                    save_location
+                    save_location,
+                    save_pattern,
+                    exclude_patterns,
+                    install_path,
+                    database_id
                 ],
             )
             .map_err(|e| format!("Failed to update game: {}", e))?;
@@ -1347,8 +2462,9 @@ pub async fn add_game_to_library(game_info: serde_json::Value) -> Result<(), Sav
                 "INSERT INTO games (
                     id, title, cover_image, platform, last_played, save_count,
                     size, status, category, is_favorite, save_location,
-                    backup_location, last_backup_time
-                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, NULL, NULL)",
+                    backup_location, last_backup_time, save_pattern, exclude_patterns,
+                    install_path, database_id
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, NULL, NULL, ?12, ?13, ?14, ?15)",
                 params![
                     game_id,
                     title,
@@ -1360,16 +2476,25 @@ pub async fn add_game_to_library(game_info: serde_json::Value) -> Result<(), Sav
                     status,
                     category,
                     is_favorite,
-                    save_location
+                    save_location,
+                    save_pattern,
+                    exclude_patterns,
+                    install_path,
+                    database_id
                 ],
             )
             .map_err(|e| format!("Failed to insert game: {}", e))?;
         }
 
+        game_identity::link_store_ids(conn, &game_id, &store_ids)?;
+
         Ok(())
     })
     .await
-    .map_err(|e| SaveFileError { message: e })
+    .map_err(|e| SaveFileError { message: e })?;
+
+    save_watcher::refresh().await;
+    Ok(())
 }
 
 // Deprecated - use add_game_to_library instead
@@ -1415,6 +2540,15 @@ pub async fn add_game_manually(
         cover_image
     };
     
+    // Manually added games get their own store id, plus the Steam one if known. The Steam id
+    // also keys the game's save game database entry
+    let mut store_ids = vec![StoreId::new("manual", &game_id)];
+    let mut database_id = None;
+    if !steam_id.is_empty() && steam_id != "0" {
+        store_ids.push(StoreId::new("steam", &steam_id));
+        database_id = Some(steam_id.clone());
+    }
+
     // Create backup directory for the game
     let backup_dir = get_saves_directory()?.join(&game_id);
     
@@ -1438,6 +2572,8 @@ pub async fn add_game_manually(
         "save_location": expanded_save_path,
         "save_pattern": save_pattern,
         "backup_location": backup_dir.to_string_lossy().to_string(),
+        "store_ids": store_ids,
+        "database_id": database_id,
     });
     
     // Use the existing add_game_to_library function
@@ -1455,6 +2591,12 @@ pub async fn read_file_as_bytes(file_path: String) -> Result<Vec<u8>, SaveFileEr
         Ok(metadata) => {
             println!("File exists. Size: {} bytes", metadata.len());
             
+            // Backup store manifests are zipped from the files they reference
+            if backup_store::is_manifest(Path::new(&file_path)) {
+                println!("Path is a backup manifest, creating zip file");
+                return create_zip_from_manifest(&file_path).await;
+            }
+
             // If it's a directory, we need to zip it first
             if metadata.is_dir() {
                 println!("Path is a directory, creating zip file");
@@ -1483,6 +2625,47 @@ pub async fn read_file_as_bytes(file_path: String) -> Result<Vec<u8>, SaveFileEr
     }
 }
 
+// Zip the files of a backup in the backup store, laid out as they were backed up
+async fn create_zip_from_manifest(manifest_path: &str) -> Result<Vec<u8>, SaveFileError> {
+    use std::io::Write;
+    use zip::write::FileOptions;
+    use zip::ZipWriter;
+
+    let saves_dir = get_saves_directory()?;
+    let manifest = backup_store::read_manifest(Path::new(manifest_path)).map_err(|e| SaveFileError {
+        message: format!("Failed to read backup manifest: {}", e),
+    })?;
+
+    let mut buffer = std::io::Cursor::new(Vec::new());
+    {
+        let mut zip = ZipWriter::new(&mut buffer);
+        let options = FileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .unix_permissions(0o755);
+
+        for file in &manifest.files {
+            zip.start_file(file.path.as_str(), options)
+                .map_err(|e| SaveFileError {
+                    message: format!("Failed to add file to zip: {}", e),
+                })?;
+            let contents = backup_store::read_object(&saves_dir, file).map_err(|e| SaveFileError {
+                message: format!("Failed to read backed up file {}: {}", file.path, e),
+            })?;
+            zip.write_all(&contents).map_err(|e| SaveFileError {
+                message: format!("Failed to write file to zip: {}", e),
+            })?;
+        }
+
+        zip.finish().map_err(|e| SaveFileError {
+            message: format!("Failed to finish zip: {}", e),
+        })?;
+    }
+
+    let bytes = buffer.into_inner();
+    println!("Created zip of {} files ({} bytes)", manifest.files.len(), bytes.len());
+    Ok(bytes)
+}
+
 // Helper function to create a zip file from a directory
 async fn create_zip_from_directory(dir_path: &str) -> Result<Vec<u8>, SaveFileError> {
     use std::io::{Write, Seek};
@@ -1600,6 +2783,113 @@ fn copy_dir_recursive(src: &PathBuf, dst: &PathBuf) -> Result<u64, std::io::Erro
     Ok(total_size)
 }
 
+// List the files of a save directory matched by the location's include/exclude globs, as
+// (path relative to the backup, source path). `prefix` is prepended to the relative paths.
+fn collect_save_files(
+    src: &Path,
+    prefix: &str,
+    location: &ResolvedLocation,
+    files: &mut Vec<(String, PathBuf)>,
+) {
+    for entry in walkdir::WalkDir::new(src).into_iter().filter_map(Result::ok) {
+        let Ok(relative) = entry.path().strip_prefix(src) else {
+            continue;
+        };
+        if !entry.file_type().is_file() || !location.is_included(relative) {
+            continue;
+        }
+
+        let relative = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let path = if prefix.is_empty() {
+            relative
+        } else {
+            format!("{}/{}", prefix, relative)
+        };
+        files.push((path, entry.path().to_path_buf()));
+    }
+}
+
+// Names of a game's backups: manifests in the backup store and legacy backup directories
+fn list_backup_names(game_saves_dir: &Path) -> Result<Vec<String>, SaveFileError> {
+    let entries = fs::read_dir(game_saves_dir).map_err(|e| SaveFileError {
+        message: format!("Failed to read saves directory: {}", e),
+    })?;
+
+    Ok(entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            match backup_store::save_id_of_manifest(&name) {
+                Some(save_id) => Some(save_id.to_string()),
+                None if entry.path().is_dir() => Some(name),
+                None => None,
+            }
+        })
+        .filter(|name| name.starts_with("backup_"))
+        .collect())
+}
+
+// Remove a backup's manifest, or its directory for backups made before the backup store.
+// Objects it referenced stay until garbage collection.
+fn remove_backup(game_saves_dir: &Path, save_id: &str) -> Result<(), SaveFileError> {
+    let manifest_path = backup_store::manifest_path(game_saves_dir, save_id);
+    if manifest_path.exists() {
+        fs::remove_file(&manifest_path).map_err(|e| SaveFileError {
+            message: format!("Failed to delete backup manifest: {}", e),
+        })?;
+        println!("Deleted backup manifest: {:?}", manifest_path);
+        return Ok(());
+    }
+
+    let save_path = safe_join_path(game_saves_dir, save_id)?;
+    if save_path.is_dir() {
+        fs::remove_dir_all(&save_path).map_err(|e| SaveFileError {
+            message: format!("Failed to delete save directory: {}", e),
+        })?;
+    } else if save_path.exists() {
+        fs::remove_file(&save_path).map_err(|e| SaveFileError {
+            message: format!("Failed to delete save file: {}", e),
+        })?;
+    }
+    println!("Deleted save file from disk: {:?}", save_path);
+    Ok(())
+}
+
+// Copy a save directory, keeping only the files matched by the location's include/exclude globs
+fn copy_save_dir(
+    src: &PathBuf,
+    dst: &PathBuf,
+    location: &ResolvedLocation,
+) -> Result<u64, std::io::Error> {
+    if location.includes_everything() && location.exclude.is_empty() {
+        return copy_dir_recursive(src, dst);
+    }
+
+    let mut total_size = 0u64;
+    create_dir_all(dst)?;
+
+    for entry in walkdir::WalkDir::new(src).into_iter().filter_map(Result::ok) {
+        let Ok(relative) = entry.path().strip_prefix(src) else {
+            continue;
+        };
+        if !entry.file_type().is_file() || !location.is_included(relative) {
+            continue;
+        }
+
+        let dst_path = dst.join(relative);
+        if let Some(parent) = dst_path.parent() {
+            create_dir_all(parent)?;
+        }
+        total_size += fs::copy(entry.path(), &dst_path)?;
+    }
+
+    Ok(total_size)
+}
+
 #[tauri::command]
 pub async fn open_save_location(game_id: String, backup: bool) -> Result<(), SaveFileError> {
     // Validate game_id to prevent path traversal
@@ -1875,6 +3165,13 @@ pub async fn add_community_save(
         file_path: extracted_path,
         origin_path: String::new(), // Community saves don't have origin path
         cloud: Some("community".to_string()), // Mark as community save
+        steam_account_id: None,
+        steam_persona_name: None,
+        verification_status: None,
+        verified_at: None,
+        pinned: false,
+        label: None,
+        notes: None,
     };
     
     // Add to database
@@ -2042,7 +3339,7 @@ pub async fn restore_community_save(game_id: String, community_save_id: String)
     let save_location = if !game.save_location.is_empty() {
         game.save_location.clone()
     } else {
-        get_save_location_from_config(&game_id)?
+        get_save_location_from_config(&game, &SteamLibraries::load())?
     };
     
     let origin_path = safe_expand_tilde(&save_location)?;
@@ -2084,3 +3381,51 @@ pub async fn restore_community_save(game_id: String, community_save_id: String)
     println!("Community save restored successfully");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expired_backups_per_steam_account() {
+        let backup = |id: &str, hour: u32, account: Option<&str>| {
+            let mut save = SaveFile::new(
+                "1245620".to_string(),
+                id.to_string(),
+                0,
+                String::new(),
+                String::new(),
+            );
+            save.created_at = Utc
+                .with_ymd_and_hms(2024, 6, 1, hour, 0, 0)
+                .unwrap()
+                .to_rfc3339();
+            save.steam_account_id = account.map(str::to_string);
+            save
+        };
+        // Account A backs up every hour, account B only twice, long ago
+        let mut saves: Vec<SaveFile> = (1..=6)
+            .map(|hour| backup(&format!("a_{}", hour), 10 + hour, Some("1")))
+            .collect();
+        saves.push(backup("b_1", 1, Some("2")));
+        saves.push(backup("b_2", 2, Some("2")));
+        let policy = RetentionPolicy {
+            keep_last: 2,
+            ..Default::default()
+        };
+
+        let mut expired = expired_backups(&saves, &policy);
+        expired.sort();
+        assert_eq!(expired, vec!["a_1", "a_2", "a_3", "a_4"]);
+    }
+
+    #[test]
+    fn test_backup_in_progress() {
+        let first = BackupInProgress::start("test_in_progress");
+        assert!(first.is_some());
+        assert!(BackupInProgress::start("test_in_progress").is_none());
+        assert!(BackupInProgress::start("test_other").is_some());
+        drop(first);
+        assert!(BackupInProgress::start("test_in_progress").is_some());
+    }
+}
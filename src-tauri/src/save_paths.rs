@@ -0,0 +1,481 @@
+use crate::game_scanner::{expand_tilde, resolve_save_location_path, steam_roots};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::path::Path;
+
+// Entries of the save game database (save_game_location.json) and the resolver that turns
+// their locations into paths on this machine. The scanner, backup and restore all resolve
+// locations through here.
+//
+// Each OS takes a single location or a list of them. A location is either a path or an
+// object with include/exclude globs, which override the entry's `save_pattern`/`exclude`:
+//
+//   "linux": [
+//     "<steamRoot>/userdata/<steamUserId>/1245620/remote",
+//     { "path": "<xdgData>/EldenRing", "include": ["*.sl2"], "exclude": ["*.bak"] }
+//   ]
+
+const HOME: &str = "<home>";
+const XDG_DATA: &str = "<xdgData>";
+const XDG_CONFIG: &str = "<xdgConfig>";
+const STEAM_ROOT: &str = "<steamRoot>";
+const STEAM_USER_ID: &str = "<steamUserId>";
+const INSTALL_DIR: &str = "<installDir>";
+const WIN_APP_DATA: &str = "<winAppData>";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct GameEntry {
+    pub steam_id: String,
+    pub name: String,
+    pub save_locations: SaveLocations,
+    // Include globs for locations that don't set their own
+    #[serde(default = "default_save_pattern")]
+    pub save_pattern: Vec<String>,
+    // Exclude globs applied to every location
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct SaveLocations {
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub macos: Vec<LocationSpec>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub windows: Vec<LocationSpec>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub linux: Vec<LocationSpec>,
+}
+
+// A configured location before placeholders are expanded
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "RawLocationSpec")]
+pub(crate) struct LocationSpec {
+    pub path: String,
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    // Only used for games from this store (steam, epic, gog, ...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub store: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawLocationSpec {
+    Path(String),
+    Detailed {
+        path: String,
+        #[serde(default)]
+        include: Vec<String>,
+        #[serde(default)]
+        exclude: Vec<String>,
+        #[serde(default)]
+        store: Option<String>,
+    },
+}
+
+impl From<RawLocationSpec> for LocationSpec {
+    fn from(raw: RawLocationSpec) -> Self {
+        match raw {
+            RawLocationSpec::Path(path) => LocationSpec {
+                path,
+                include: Vec::new(),
+                exclude: Vec::new(),
+                store: None,
+            },
+            RawLocationSpec::Detailed {
+                path,
+                include,
+                exclude,
+                store,
+            } => LocationSpec {
+                path,
+                include,
+                exclude,
+                store,
+            },
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(LocationSpec),
+    Many(Vec<LocationSpec>),
+}
+
+// Accept a single location or a list; empty paths (the old "no location" value) are dropped
+fn one_or_many<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<LocationSpec>, D::Error> {
+    let specs = match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(spec) => vec![spec],
+        OneOrMany::Many(specs) => specs,
+    };
+    Ok(specs
+        .into_iter()
+        .filter(|spec| !spec.path.trim().is_empty())
+        .collect())
+}
+
+fn default_save_pattern() -> Vec<String> {
+    vec!["*".to_string()]
+}
+
+impl GameEntry {
+    // Locations configured for the current platform
+    pub fn platform_locations(&self) -> &[LocationSpec] {
+        #[cfg(target_os = "macos")]
+        return &self.save_locations.macos;
+
+        #[cfg(target_os = "windows")]
+        return &self.save_locations.windows;
+
+        #[cfg(target_os = "linux")]
+        return &self.save_locations.linux;
+
+        #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+        return &[];
+    }
+
+    fn filters(&self, spec: &LocationSpec) -> (Vec<String>, Vec<String>) {
+        let include = if spec.include.is_empty() {
+            self.save_pattern.clone()
+        } else {
+            spec.include.clone()
+        };
+        let mut exclude = self.exclude.clone();
+        exclude.extend(spec.exclude.iter().cloned());
+        (include, exclude)
+    }
+}
+
+// What is known about the game when resolving its locations
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct ResolveContext<'a> {
+    // Steam installation the game belongs to; all installations are tried when unknown
+    pub steam_root: Option<&'a Path>,
+    // Steam account to resolve for; every account (`*`) when unknown
+    pub steam_user_id: Option<&'a str>,
+    pub install_dir: Option<&'a Path>,
+    // Store the game was found in; locations for other stores are skipped
+    pub store: Option<&'a str>,
+}
+
+// A location with placeholders expanded. The path may still start with `~` and contain
+// glob wildcards.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ResolvedLocation {
+    pub path: String,
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+}
+
+impl ResolvedLocation {
+    pub fn has_wildcard(&self) -> bool {
+        self.path.contains('*')
+    }
+
+    // Whether every file below the location is saved
+    pub fn includes_everything(&self) -> bool {
+        self.include.len() == 1 && self.include[0] == "*"
+    }
+
+    // Whether a file, relative to the location, is part of the save
+    pub fn is_included(&self, relative: &Path) -> bool {
+        let matches = |pattern: &String| {
+            glob::Pattern::new(pattern)
+                .map(|pattern| pattern.matches_path(relative))
+                .unwrap_or(false)
+        };
+        (self.includes_everything() || self.include.iter().any(matches))
+            && !self.is_excluded(relative)
+    }
+
+    pub fn is_excluded(&self, relative: &Path) -> bool {
+        self.exclude.iter().any(|pattern| {
+            glob::Pattern::new(pattern)
+                .map(|pattern| pattern.matches_path(relative))
+                .unwrap_or(false)
+        })
+    }
+}
+
+// Resolve the entry's locations for the current platform, in configured order. Locations
+// using a placeholder that can't be resolved here (e.g. <installDir> for an unknown install)
+// are skipped.
+pub(crate) fn resolve_locations(
+    entry: &GameEntry,
+    context: &ResolveContext,
+) -> Vec<ResolvedLocation> {
+    entry
+        .platform_locations()
+        .iter()
+        .filter(|spec| applies_to_store(spec, context))
+        .filter_map(|spec| {
+            let path = expand_placeholders(&spec.path, context)?;
+            let (include, exclude) = entry.filters(spec);
+            Some(ResolvedLocation {
+                path,
+                include,
+                exclude,
+            })
+        })
+        .collect()
+}
+
+// Resolve the entry's Windows locations inside a Wine/Proton prefix. `resolve_in_prefix`
+// maps a Windows path (`~`, `%APPDATA%`, `C:\...`) to the existing directories in the prefix.
+#[cfg(target_os = "linux")]
+pub(crate) fn resolve_windows_locations<F>(
+    entry: &GameEntry,
+    context: &ResolveContext,
+    resolve_in_prefix: F,
+) -> Vec<ResolvedLocation>
+where
+    F: Fn(&str) -> Vec<std::path::PathBuf>,
+{
+    let mut resolved = Vec::new();
+
+    for spec in &entry.save_locations.windows {
+        if !applies_to_store(spec, context) {
+            continue;
+        }
+        let (include, exclude) = entry.filters(spec);
+
+        // Saves kept next to the game are found on the native filesystem
+        let paths = if spec.path.contains(INSTALL_DIR) {
+            expand_placeholders(&spec.path, context)
+                .map(|path| {
+                    glob::glob(&expand_tilde(&path).to_string_lossy())
+                        .map(|paths| paths.filter_map(Result::ok).collect())
+                        .unwrap_or_default()
+                })
+                .unwrap_or_default()
+        } else {
+            match windows_path(&spec.path, context) {
+                Some(path) => resolve_in_prefix(&path),
+                None => Vec::new(),
+            }
+        };
+
+        resolved.extend(paths.into_iter().filter(|path| path.is_dir()).map(|path| {
+            ResolvedLocation {
+                path: path.to_string_lossy().into_owned(),
+                include: include.clone(),
+                exclude: exclude.clone(),
+            }
+        }));
+    }
+
+    resolved
+}
+
+fn applies_to_store(spec: &LocationSpec, context: &ResolveContext) -> bool {
+    match (spec.store.as_deref(), context.store) {
+        (Some(required), Some(store)) => required.eq_ignore_ascii_case(store),
+        _ => true,
+    }
+}
+
+// Expand the placeholders in a location for the current machine
+pub(crate) fn expand_placeholders(location: &str, context: &ResolveContext) -> Option<String> {
+    let mut expanded = location.replace(STEAM_USER_ID, context.steam_user_id.unwrap_or("*"));
+
+    if expanded.contains(HOME) {
+        expanded = expanded.replace(HOME, "~");
+    }
+    if expanded.contains(XDG_DATA) {
+        expanded = expanded.replace(XDG_DATA, &dirs::data_dir()?.to_string_lossy());
+    }
+    if expanded.contains(XDG_CONFIG) {
+        expanded = expanded.replace(XDG_CONFIG, &dirs::config_dir()?.to_string_lossy());
+    }
+    if expanded.contains(WIN_APP_DATA) {
+        if !cfg!(windows) {
+            return None;
+        }
+        expanded = expanded.replace(WIN_APP_DATA, &dirs::data_dir()?.to_string_lossy());
+    }
+    if expanded.contains(INSTALL_DIR) {
+        expanded = expanded.replace(INSTALL_DIR, &context.install_dir?.to_string_lossy());
+    }
+
+    let expanded = expand_steam_root(&expanded, context.steam_root)?;
+
+    if let Some(start) = expanded
+        .find('<')
+        .filter(|&start| expanded[start..].contains('>'))
+    {
+        println!(
+            "Unknown placeholder in save location: {}",
+            &expanded[start..]
+        );
+        return None;
+    }
+
+    Some(expanded)
+}
+
+// Resolve <steamRoot>, or a location spelled out inside a default Steam install
+// (~/.local/share/Steam/...), against the game's Steam root. Without a known root, the
+// first installation where the location exists is used.
+fn expand_steam_root(location: &str, steam_root: Option<&Path>) -> Option<String> {
+    if !location.contains(STEAM_ROOT) {
+        let resolved = resolve_save_location_path(location, steam_root);
+        return Some(if resolved == expand_tilde(location) {
+            location.to_string()
+        } else {
+            resolved.to_string_lossy().into_owned()
+        });
+    }
+
+    let roots = match steam_root {
+        Some(root) => vec![root.to_path_buf()],
+        None => steam_roots(),
+    };
+    let candidates: Vec<String> = roots
+        .iter()
+        .map(|root| location.replace(STEAM_ROOT, &root.to_string_lossy()))
+        .collect();
+
+    candidates
+        .iter()
+        .find(|candidate| location_exists(candidate))
+        .or(candidates.first())
+        .cloned()
+}
+
+// Translate a Windows location into the form understood inside a prefix
+#[cfg(target_os = "linux")]
+fn windows_path(location: &str, context: &ResolveContext) -> Option<String> {
+    let path = location
+        .replace(STEAM_USER_ID, context.steam_user_id.unwrap_or("*"))
+        .replace(HOME, "~")
+        .replace(WIN_APP_DATA, "%APPDATA%");
+    (!path.contains('<')).then_some(path)
+}
+
+// Whether a location (which may contain wildcards) points at existing data
+pub(crate) fn location_exists(location: &str) -> bool {
+    let path = expand_tilde(location);
+    if location.contains('*') {
+        glob::glob(&path.to_string_lossy())
+            .map(|mut paths| paths.any(|p| p.is_ok()))
+            .unwrap_or(false)
+    } else {
+        path.exists()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_parse_legacy_and_rich_entries() {
+        let config: HashMap<String, GameEntry> = serde_json::from_str(
+            r#"{
+                "3489700": {
+                    "steam_id": "3489700",
+                    "name": "Stellar Blade",
+                    "save_locations": {
+                        "macos": "",
+                        "windows": "~/AppData/Local/SB/Saved/SaveGames",
+                        "linux": "~/.local/share/Steam/userdata/*/3489700/remote"
+                    },
+                    "save_pattern": ["*"]
+                },
+                "1245620": {
+                    "steam_id": "1245620",
+                    "name": "Elden Ring",
+                    "save_locations": {
+                        "linux": [
+                            "<steamRoot>/userdata/<steamUserId>/1245620/remote",
+                            { "path": "<xdgData>/EldenRing", "include": ["*.sl2"], "exclude": ["*.bak"] }
+                        ]
+                    },
+                    "exclude": ["*.log"]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let legacy = &config["3489700"];
+        assert!(legacy.save_locations.macos.is_empty());
+        assert_eq!(legacy.save_locations.windows.len(), 1);
+        assert_eq!(
+            legacy.save_locations.linux[0].path,
+            "~/.local/share/Steam/userdata/*/3489700/remote"
+        );
+
+        let rich = &config["1245620"];
+        assert_eq!(rich.save_pattern, vec!["*".to_string()]);
+        assert_eq!(rich.save_locations.linux.len(), 2);
+        assert_eq!(
+            rich.filters(&rich.save_locations.linux[0]),
+            (vec!["*".to_string()], vec!["*.log".to_string()])
+        );
+        assert_eq!(
+            rich.filters(&rich.save_locations.linux[1]),
+            (
+                vec!["*.sl2".to_string()],
+                vec!["*.log".to_string(), "*.bak".to_string()]
+            )
+        );
+    }
+
+    #[test]
+    fn test_expand_placeholders() {
+        let steam_root =
+            PathBuf::from("/home/deck/.var/app/com.valvesoftware.Steam/.local/share/Steam");
+        let install_dir = PathBuf::from("/games/celeste");
+        let context = ResolveContext {
+            steam_root: Some(&steam_root),
+            steam_user_id: Some("22202"),
+            install_dir: Some(&install_dir),
+            store: None,
+        };
+
+        assert_eq!(
+            expand_placeholders("<steamRoot>/userdata/<steamUserId>/730/remote", &context)
+                .as_deref(),
+            Some("/home/deck/.var/app/com.valvesoftware.Steam/.local/share/Steam/userdata/22202/730/remote")
+        );
+        assert_eq!(
+            expand_placeholders("<home>/.celeste", &context).as_deref(),
+            Some("~/.celeste")
+        );
+        assert_eq!(
+            expand_placeholders("<installDir>/Saves", &context).as_deref(),
+            Some("/games/celeste/Saves")
+        );
+        assert_eq!(
+            expand_placeholders("<xdgConfig>/unity3d", &context),
+            dirs::config_dir().map(|dir| format!("{}/unity3d", dir.to_string_lossy()))
+        );
+        assert_eq!(
+            expand_placeholders("<steamUserId>", &ResolveContext::default()).as_deref(),
+            Some("*")
+        );
+        assert_eq!(
+            expand_placeholders("<installDir>/Saves", &ResolveContext::default()),
+            None
+        );
+        assert_eq!(expand_placeholders("<unknown>/Saves", &context), None);
+    }
+
+    #[test]
+    fn test_location_filters() {
+        let location = ResolvedLocation {
+            path: "~/saves".to_string(),
+            include: vec!["*.sav".to_string(), "profiles/*".to_string()],
+            exclude: vec!["*backup*".to_string()],
+        };
+
+        assert!(location.is_included(Path::new("slot1.sav")));
+        assert!(location.is_included(Path::new("profiles/default.cfg")));
+        assert!(!location.is_included(Path::new("slot1.sav.backup")));
+        assert!(!location.is_included(Path::new("settings.ini")));
+        assert!(!location.includes_everything());
+    }
+}
@@ -0,0 +1,256 @@
+use crate::backup_scheduler;
+use crate::game_scanner::SteamLibraries;
+use crate::save_manager;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+// Watches the save locations of games with `watch_saves` enabled. Changes are collected per
+// game, and once a game's saves have been quiet for `watch_quiet_seconds` it is backed up.
+// backup_save skips saves that match the latest backup, so extra triggers add no backups.
+
+// How often pending changes are checked for quiet games
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+struct SaveWatcher {
+    watcher: RecommendedWatcher,
+    // Watched save locations and the game each belongs to
+    roots: HashMap<PathBuf, String>,
+    // Paths registered with the watcher: save directories, or the parent of a single save file
+    targets: HashSet<PathBuf>,
+}
+
+static WATCHER: Lazy<Mutex<Option<SaveWatcher>>> = Lazy::new(|| Mutex::new(None));
+
+impl SaveWatcher {
+    fn update(&mut self, roots: HashMap<PathBuf, String>) {
+        let mut targets: HashMap<PathBuf, RecursiveMode> = HashMap::new();
+        for root in roots.keys() {
+            let (target, mode) = watch_target(root);
+            let entry = targets.entry(target).or_insert(mode);
+            if mode == RecursiveMode::Recursive {
+                *entry = mode;
+            }
+        }
+
+        for target in self.targets.iter() {
+            if !targets.contains_key(target) {
+                if let Err(e) = self.watcher.unwatch(target) {
+                    println!("Failed to stop watching {:?}: {}", target, e);
+                }
+            }
+        }
+        let mut watched = HashSet::new();
+        for (target, mode) in targets {
+            if !self.targets.contains(&target) {
+                if let Err(e) = self.watcher.watch(&target, mode) {
+                    println!("Failed to watch {:?}: {}", target, e);
+                    continue;
+                }
+            }
+            watched.insert(target);
+        }
+
+        println!(
+            "Watching {} save locations of {} games",
+            roots.len(),
+            roots.values().collect::<HashSet<_>>().len()
+        );
+        self.targets = watched;
+        self.roots = roots;
+    }
+}
+
+fn watch_target(root: &Path) -> (PathBuf, RecursiveMode) {
+    if root.is_dir() {
+        (root.to_path_buf(), RecursiveMode::Recursive)
+    } else {
+        let parent = root.parent().unwrap_or(root);
+        (parent.to_path_buf(), RecursiveMode::NonRecursive)
+    }
+}
+
+fn game_for_path<'a>(roots: &'a HashMap<PathBuf, String>, path: &Path) -> Option<&'a String> {
+    roots
+        .iter()
+        .find(|(root, _)| path.starts_with(root))
+        .map(|(_, game_id)| game_id)
+}
+
+// Take the games whose last change is at least `quiet` ago
+fn quiet_games(
+    pending: &mut HashMap<String, Instant>,
+    now: Instant,
+    quiet: Duration,
+) -> Vec<String> {
+    let ready: Vec<String> = pending
+        .iter()
+        .filter(|(_, changed)| now.duration_since(**changed) >= quiet)
+        .map(|(game_id, _)| game_id.clone())
+        .collect();
+    for game_id in &ready {
+        pending.remove(game_id);
+    }
+    ready
+}
+
+// Start watching and backing up the saves of games that have watching enabled
+pub fn start(app: AppHandle) {
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel::<Vec<PathBuf>>();
+    let watcher = notify::recommended_watcher(move |result: notify::Result<Event>| match result {
+        Ok(event) => {
+            if matches!(
+                event.kind,
+                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+            ) {
+                let _ = sender.send(event.paths);
+            }
+        }
+        Err(e) => println!("Save watcher error: {}", e),
+    });
+    let watcher = match watcher {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            println!("Failed to start save watcher: {}", e);
+            return;
+        }
+    };
+    if let Ok(mut state) = WATCHER.lock() {
+        *state = Some(SaveWatcher {
+            watcher,
+            roots: HashMap::new(),
+            targets: HashSet::new(),
+        });
+    }
+
+    tauri::async_runtime::spawn(async move {
+        refresh().await;
+
+        // Time of the last change seen per game
+        let mut pending: HashMap<String, Instant> = HashMap::new();
+        loop {
+            match tokio::time::timeout(POLL_INTERVAL, receiver.recv()).await {
+                Ok(Some(paths)) => {
+                    if let Ok(state) = WATCHER.lock() {
+                        if let Some(state) = state.as_ref() {
+                            for path in &paths {
+                                if let Some(game_id) = game_for_path(&state.roots, path) {
+                                    pending.insert(game_id.clone(), Instant::now());
+                                }
+                            }
+                        }
+                    }
+                }
+                Ok(None) => break,
+                Err(_) => {} // Nothing changed within the poll interval
+            }
+            if pending.is_empty() {
+                continue;
+            }
+
+            let quiet_seconds = save_manager::load_backup_settings()
+                .await
+                .map(|settings| settings.watch_quiet_seconds)
+                .unwrap_or(10);
+            let ready = quiet_games(
+                &mut pending,
+                Instant::now(),
+                Duration::from_secs(quiet_seconds),
+            );
+            for game_id in ready {
+                let Ok(game) = save_manager::get_game_by_id(game_id).await else {
+                    continue;
+                };
+                println!("Saves of {} ({}) changed, backing up", game.title, game.id);
+                backup_scheduler::auto_backup(&app, game.id, game.title, "watcher").await;
+            }
+        }
+    });
+}
+
+// Watch the save locations of the library's games that have watching enabled. Called whenever
+// the library changes; does nothing until the watcher is started.
+pub async fn refresh() {
+    let started = WATCHER.lock().map(|state| state.is_some()).unwrap_or(false);
+    if !started {
+        return;
+    }
+
+    let games = match save_manager::get_all_games().await {
+        Ok(games) => games,
+        Err(e) => {
+            println!("Failed to load games for the save watcher: {}", e.message);
+            return;
+        }
+    };
+    // Resolving save locations touches the file system
+    let roots = tokio::task::spawn_blocking(move || {
+        let steam_libraries = SteamLibraries::load();
+        games
+            .iter()
+            .filter(|game| game.watch_saves)
+            .flat_map(|game| {
+                save_manager::game_save_paths(game, &steam_libraries)
+                    .into_iter()
+                    .map(|path| (path, game.id.clone()))
+            })
+            .collect::<HashMap<_, _>>()
+    })
+    .await
+    .unwrap_or_default();
+
+    if let Ok(mut state) = WATCHER.lock() {
+        if let Some(state) = state.as_mut() {
+            state.update(roots);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changes_are_debounced_per_game() {
+        let roots = HashMap::from([
+            (PathBuf::from("/saves/elden_ring"), "1245620".to_string()),
+            (
+                PathBuf::from("/saves/hades/Profile1.sav"),
+                "1145360".to_string(),
+            ),
+        ]);
+        assert_eq!(
+            game_for_path(&roots, Path::new("/saves/elden_ring/76561198/ER0000.sl2")),
+            Some(&"1245620".to_string())
+        );
+        assert_eq!(
+            game_for_path(&roots, Path::new("/saves/hades/Profile1.sav")),
+            Some(&"1145360".to_string())
+        );
+        assert_eq!(
+            game_for_path(&roots, Path::new("/saves/hades/Profile2.sav")),
+            None
+        );
+
+        let start = Instant::now();
+        let quiet = Duration::from_secs(10);
+        let mut pending = HashMap::from([
+            ("1245620".to_string(), start),
+            ("1145360".to_string(), start + Duration::from_secs(5)),
+        ]);
+        assert!(quiet_games(&mut pending, start + Duration::from_secs(9), quiet).is_empty());
+        assert_eq!(
+            quiet_games(&mut pending, start + Duration::from_secs(12), quiet),
+            vec!["1245620".to_string()]
+        );
+        assert_eq!(
+            quiet_games(&mut pending, start + Duration::from_secs(15), quiet),
+            vec!["1145360".to_string()]
+        );
+        assert!(pending.is_empty());
+    }
+}
@@ -0,0 +1,285 @@
+use crate::db;
+use rusqlite::params;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Mutex;
+
+// Persistent scan cache. Results of expensive scan work (parsing manifests) are stored in
+// SQLite keyed by kind and path, together with the path's modification time. An entry is
+// reused while the mtime is unchanged.
+
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    mtime: i64,
+    data: String,
+}
+
+#[derive(Default)]
+pub struct ScanCache {
+    entries: Mutex<HashMap<(String, String), CacheEntry>>,
+    // Entries read or written during this scan; everything else is dropped on save
+    touched: Mutex<HashSet<(String, String)>>,
+    force_full_rescan: bool,
+}
+
+// Games added, removed or changed since the previous scan
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ScanChanges {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<String>,
+}
+
+// Modification time of a path in nanoseconds since the Unix epoch
+pub fn modified_time(path: &Path) -> Option<i64> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    let since_epoch = modified.duration_since(std::time::UNIX_EPOCH).ok()?;
+    Some(since_epoch.as_nanos() as i64)
+}
+
+impl ScanCache {
+    fn new(entries: HashMap<(String, String), CacheEntry>, force_full_rescan: bool) -> Self {
+        Self {
+            entries: Mutex::new(entries),
+            touched: Mutex::new(HashSet::new()),
+            force_full_rescan,
+        }
+    }
+
+    // Load the cache from the database. A forced full rescan ignores every cached entry.
+    pub async fn load(force_full_rescan: bool) -> Self {
+        let entries = db::execute_blocking(|conn| {
+            let mut stmt = conn
+                .prepare("SELECT kind, path, mtime, data FROM scan_cache")
+                .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+            let entries = stmt
+                .query_map([], |row| {
+                    Ok((
+                        (row.get::<_, String>(0)?, row.get::<_, String>(1)?),
+                        CacheEntry {
+                            mtime: row.get(2)?,
+                            data: row.get(3)?,
+                        },
+                    ))
+                })
+                .map_err(|e| format!("Failed to query scan cache: {}", e))?
+                .collect::<Result<HashMap<_, _>, _>>()
+                .map_err(|e| format!("Failed to collect scan cache: {}", e))?;
+
+            Ok(entries)
+        })
+        .await
+        .unwrap_or_else(|e| {
+            println!("Failed to load scan cache, doing a full scan: {}", e);
+            HashMap::new()
+        });
+
+        println!(
+            "Loaded {} scan cache entries (force full rescan: {})",
+            entries.len(),
+            force_full_rescan
+        );
+        Self::new(entries, force_full_rescan)
+    }
+
+    // Return the cached value for `path` if its mtime is unchanged, otherwise compute it and
+    // store the result. Paths that don't exist are never cached.
+    pub fn cached<T, F>(&self, kind: &str, path: &Path, compute: F) -> T
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> T,
+    {
+        let Some(mtime) = modified_time(path) else {
+            return compute();
+        };
+        let key = (kind.to_string(), path.to_string_lossy().into_owned());
+
+        if !self.force_full_rescan {
+            let cached = self
+                .entries
+                .lock()
+                .ok()
+                .and_then(|entries| entries.get(&key).cloned())
+                .filter(|entry| entry.mtime == mtime)
+                .and_then(|entry| serde_json::from_str(&entry.data).ok());
+
+            if let Some(value) = cached {
+                if let Ok(mut touched) = self.touched.lock() {
+                    touched.insert(key);
+                }
+                return value;
+            }
+        }
+
+        let value = compute();
+        if let Ok(data) = serde_json::to_string(&value) {
+            if let Ok(mut entries) = self.entries.lock() {
+                entries.insert(key.clone(), CacheEntry { mtime, data });
+            }
+            if let Ok(mut touched) = self.touched.lock() {
+                touched.insert(key);
+            }
+        }
+        value
+    }
+
+    // Write the entries used by this scan back to the database, dropping stale ones
+    pub async fn save(self) -> Result<(), String> {
+        let touched = self
+            .touched
+            .into_inner()
+            .map_err(|_| "Failed to acquire scan cache lock".to_string())?;
+        let entries: Vec<((String, String), CacheEntry)> = self
+            .entries
+            .into_inner()
+            .map_err(|_| "Failed to acquire scan cache lock".to_string())?
+            .into_iter()
+            .filter(|(key, _)| touched.contains(key))
+            .collect();
+
+        db::execute_blocking(move |conn| {
+            let tx = conn
+                .unchecked_transaction()
+                .map_err(|e| format!("Failed to start transaction: {}", e))?;
+            tx.execute("DELETE FROM scan_cache", [])
+                .map_err(|e| format!("Failed to clear scan cache: {}", e))?;
+            for ((kind, path), entry) in &entries {
+                tx.execute(
+                    "INSERT INTO scan_cache (kind, path, mtime, data) VALUES (?1, ?2, ?3, ?4)",
+                    params![kind, path, entry.mtime, entry.data],
+                )
+                .map_err(|e| format!("Failed to save scan cache: {}", e))?;
+            }
+            tx.commit()
+                .map_err(|e| format!("Failed to save scan cache: {}", e))?;
+            Ok(())
+        })
+        .await
+    }
+}
+
+// Compare scan results (game id and serialized game) against the previous scan
+pub fn diff_scan_results(
+    previous: &HashMap<String, String>,
+    current: &HashMap<String, String>,
+) -> ScanChanges {
+    let mut changes = ScanChanges::default();
+
+    for (game_id, data) in current {
+        match previous.get(game_id) {
+            None => changes.added.push(game_id.clone()),
+            Some(old) if old != data => changes.changed.push(game_id.clone()),
+            Some(_) => {}
+        }
+    }
+    changes.removed = previous
+        .keys()
+        .filter(|game_id| !current.contains_key(*game_id))
+        .cloned()
+        .collect();
+
+    changes.added.sort();
+    changes.removed.sort();
+    changes.changed.sort();
+    changes
+}
+
+// Store this scan's results and report what changed since the previous scan
+pub async fn record_scan_results(current: HashMap<String, String>) -> Result<ScanChanges, String> {
+    db::execute_blocking(move |conn| {
+        let previous = {
+            let mut stmt = conn
+                .prepare("SELECT game_id, data FROM scan_results")
+                .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+            let previous = stmt
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+                .map_err(|e| format!("Failed to query scan results: {}", e))?
+                .collect::<Result<HashMap<String, String>, _>>()
+                .map_err(|e| format!("Failed to collect scan results: {}", e))?;
+            previous
+        };
+
+        let changes = diff_scan_results(&previous, &current);
+
+        let tx = conn
+            .unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        tx.execute("DELETE FROM scan_results", [])
+            .map_err(|e| format!("Failed to clear scan results: {}", e))?;
+        for (game_id, data) in &current {
+            tx.execute(
+                "INSERT INTO scan_results (game_id, data) VALUES (?1, ?2)",
+                params![game_id, data],
+            )
+            .map_err(|e| format!("Failed to save scan results: {}", e))?;
+        }
+        tx.commit()
+            .map_err(|e| format!("Failed to save scan results: {}", e))?;
+
+        Ok(changes)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_cached_reuses_until_mtime_changes() {
+        let dir = std::env::temp_dir().join(format!("rogame_scan_cache_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("appmanifest_1.acf");
+        std::fs::write(&file, "one").unwrap();
+
+        let cache = ScanCache::new(HashMap::new(), false);
+        let calls = Cell::new(0);
+        let compute = || {
+            calls.set(calls.get() + 1);
+            calls.get()
+        };
+
+        assert_eq!(cache.cached("manifest", &file, compute), 1);
+        assert_eq!(cache.cached("manifest", &file, compute), 1);
+        assert_eq!(cache.cached("other", &file, compute), 2);
+
+        // A different mtime invalidates the entry
+        let key = ("manifest".to_string(), file.to_string_lossy().into_owned());
+        cache.entries.lock().unwrap().get_mut(&key).unwrap().mtime -= 1;
+        assert_eq!(cache.cached("manifest", &file, compute), 3);
+
+        // Missing paths are computed every time
+        assert_eq!(cache.cached("manifest", &dir.join("missing"), compute), 4);
+        assert_eq!(cache.cached("manifest", &dir.join("missing"), compute), 5);
+
+        let forced = ScanCache::new(cache.entries.into_inner().unwrap(), true);
+        assert_eq!(forced.cached("manifest", &file, compute), 6);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_diff_scan_results() {
+        let previous: HashMap<String, String> = [("1", "a"), ("2", "b"), ("3", "c")]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let current: HashMap<String, String> = [("1", "a"), ("2", "changed"), ("4", "d")]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+
+        assert_eq!(
+            diff_scan_results(&previous, &current),
+            ScanChanges {
+                added: vec!["4".to_string()],
+                removed: vec!["3".to_string()],
+                changed: vec!["2".to_string()],
+            }
+        );
+    }
+}
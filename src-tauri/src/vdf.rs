@@ -0,0 +1,929 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+// Parser for Valve's text KeyValues format (libraryfolders.vdf, appmanifest_*.acf, loginusers.vdf)
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeyValue {
+    String(String),
+    Section(KeyValues),
+}
+
+// Ordered list of key/value pairs. Keys are compared case-insensitively like Steam does,
+// and duplicate keys are kept in file order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KeyValues {
+    entries: Vec<(String, KeyValue)>,
+}
+
+impl KeyValues {
+    pub fn get(&self, key: &str) -> Option<&KeyValue> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v)
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        match self.get(key) {
+            Some(KeyValue::String(value)) => Some(value.as_str()),
+            _ => None,
+        }
+    }
+
+    pub fn get_u64(&self, key: &str) -> Option<u64> {
+        self.get_str(key)
+            .and_then(|value| value.trim().parse().ok())
+    }
+
+    pub fn get_section(&self, key: &str) -> Option<&KeyValues> {
+        match self.get(key) {
+            Some(KeyValue::Section(section)) => Some(section),
+            _ => None,
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &KeyValue)> {
+        self.entries.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    pub fn sections(&self) -> impl Iterator<Item = (&str, &KeyValues)> {
+        self.iter().filter_map(|(k, v)| match v {
+            KeyValue::Section(section) => Some((k, section)),
+            KeyValue::String(_) => None,
+        })
+    }
+
+    // Flatten a section of plain string values into a map
+    pub fn string_map(&self) -> HashMap<String, String> {
+        self.iter()
+            .filter_map(|(k, v)| match v {
+                KeyValue::String(value) => Some((k.to_string(), value.clone())),
+                KeyValue::Section(_) => None,
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Text(String),
+    Open,
+    Close,
+}
+
+struct Tokenizer<'a> {
+    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+    line: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            chars: input.char_indices().peekable(),
+            line: 1,
+        }
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token>, String> {
+        loop {
+            let (_, c) = match self.chars.next() {
+                Some(next) => next,
+                None => return Ok(None),
+            };
+
+            match c {
+                '\n' => self.line += 1,
+                c if c.is_whitespace() || c == '\u{feff}' => {}
+                '{' => return Ok(Some(Token::Open)),
+                '}' => return Ok(Some(Token::Close)),
+                '/' if matches!(self.chars.peek(), Some((_, '/'))) => self.skip_line(),
+                // Platform conditionals such as [$WIN32] are ignored; the value is always kept
+                '[' => self.skip_conditional()?,
+                '"' => return self.quoted().map(|text| Some(Token::Text(text))),
+                c => return Ok(Some(Token::Text(self.unquoted(c)))),
+            }
+        }
+    }
+
+    fn skip_line(&mut self) {
+        for (_, c) in self.chars.by_ref() {
+            if c == '\n' {
+                self.line += 1;
+                break;
+            }
+        }
+    }
+
+    fn skip_conditional(&mut self) -> Result<(), String> {
+        for (_, c) in self.chars.by_ref() {
+            match c {
+                ']' => return Ok(()),
+                '\n' => break,
+                _ => {}
+            }
+        }
+        Err(format!("Unterminated conditional on line {}", self.line))
+    }
+
+    fn quoted(&mut self) -> Result<String, String> {
+        let start_line = self.line;
+        let mut text = String::new();
+
+        while let Some((_, c)) = self.chars.next() {
+            match c {
+                '"' => return Ok(text),
+                '\\' => match self.chars.next() {
+                    Some((_, 'n')) => text.push('\n'),
+                    Some((_, 't')) => text.push('\t'),
+                    Some((_, '\\')) => text.push('\\'),
+                    Some((_, '"')) => text.push('"'),
+                    Some((_, other)) => {
+                        text.push('\\');
+                        text.push(other);
+                    }
+                    None => break,
+                },
+                '\n' => {
+                    self.line += 1;
+                    text.push('\n');
+                }
+                c => text.push(c),
+            }
+        }
+
+        Err(format!(
+            "Unterminated string starting on line {}",
+            start_line
+        ))
+    }
+
+    fn unquoted(&mut self, first: char) -> String {
+        let mut text = String::from(first);
+        while let Some(&(_, c)) = self.chars.peek() {
+            if c.is_whitespace() || c == '"' || c == '{' || c == '}' {
+                break;
+            }
+            text.push(c);
+            self.chars.next();
+        }
+        text
+    }
+}
+
+// Parse a KeyValues document. The returned tree holds the top-level key(s),
+// e.g. `"libraryfolders"` or `"AppState"`.
+pub fn parse(input: &str) -> Result<KeyValues, String> {
+    let mut tokenizer = Tokenizer::new(input);
+    let root = parse_section(&mut tokenizer, false)?;
+    Ok(root)
+}
+
+fn parse_section(tokenizer: &mut Tokenizer, nested: bool) -> Result<KeyValues, String> {
+    let mut section = KeyValues::default();
+
+    loop {
+        let key = match tokenizer.next_token()? {
+            Some(Token::Text(key)) => key,
+            Some(Token::Close) if nested => return Ok(section),
+            Some(Token::Close) => {
+                return Err(format!("Unexpected '}}' on line {}", tokenizer.line));
+            }
+            Some(Token::Open) => {
+                return Err(format!(
+                    "Expected key before '{{' on line {}",
+                    tokenizer.line
+                ));
+            }
+            None if nested => return Err("Unexpected end of file inside section".to_string()),
+            None => return Ok(section),
+        };
+
+        let value = match tokenizer.next_token()? {
+            Some(Token::Text(value)) => KeyValue::String(value),
+            Some(Token::Open) => KeyValue::Section(parse_section(tokenizer, true)?),
+            Some(Token::Close) | None => {
+                return Err(format!(
+                    "Missing value for key \"{}\" on line {}",
+                    key, tokenizer.line
+                ));
+            }
+        };
+
+        section.entries.push((key, value));
+    }
+}
+
+// A single entry from steamapps/libraryfolders.vdf
+#[derive(Debug, Clone, PartialEq)]
+pub struct LibraryFolder {
+    pub path: PathBuf,
+    pub label: String,
+    pub content_id: Option<String>,
+    pub total_size: Option<u64>,
+    pub update_clean_bytes_tally: Option<u64>,
+    pub time_last_update_verified: Option<u64>,
+    // Installed app id -> size on disk in bytes
+    pub apps: Vec<(String, u64)>,
+}
+
+impl LibraryFolder {
+    pub fn app_ids(&self) -> Vec<String> {
+        self.apps.iter().map(|(app_id, _)| app_id.clone()).collect()
+    }
+}
+
+// Parse libraryfolders.vdf. Handles both the current format (numbered sections with
+// "path" and "apps") and the legacy one where entries were `"1" "D:\\SteamLibrary"`.
+pub fn parse_library_folders(input: &str) -> Result<Vec<LibraryFolder>, String> {
+    let root = parse(input)?;
+    let folders = root
+        .get_section("libraryfolders")
+        .or_else(|| root.get_section("LibraryFolders"))
+        .ok_or_else(|| "Missing \"libraryfolders\" section".to_string())?;
+
+    let mut libraries = Vec::new();
+    for (key, value) in folders.iter() {
+        // Only numbered entries are libraries ("contentstatsid" and friends are not)
+        if !key.chars().all(|c| c.is_ascii_digit()) {
+            continue;
+        }
+
+        match value {
+            KeyValue::String(path) => libraries.push(LibraryFolder {
+                path: PathBuf::from(path),
+                label: String::new(),
+                content_id: None,
+                total_size: None,
+                update_clean_bytes_tally: None,
+                time_last_update_verified: None,
+                apps: Vec::new(),
+            }),
+            KeyValue::Section(entry) => {
+                let Some(path) = entry.get_str("path") else {
+                    continue;
+                };
+
+                let apps = entry
+                    .get_section("apps")
+                    .map(|apps| {
+                        apps.iter()
+                            .filter_map(|(app_id, size)| match size {
+                                KeyValue::String(size) => {
+                                    Some((app_id.to_string(), size.parse().unwrap_or(0)))
+                                }
+                                KeyValue::Section(_) => None,
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                libraries.push(LibraryFolder {
+                    path: PathBuf::from(path),
+                    label: entry.get_str("label").unwrap_or_default().to_string(),
+                    content_id: entry.get_str("contentid").map(str::to_string),
+                    total_size: entry.get_u64("totalsize"),
+                    update_clean_bytes_tally: entry.get_u64("update_clean_bytes_tally"),
+                    time_last_update_verified: entry.get_u64("time_last_update_verified"),
+                    apps,
+                });
+            }
+        }
+    }
+
+    Ok(libraries)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstalledDepot {
+    pub depot_id: String,
+    pub manifest: String,
+    pub size: u64,
+    pub dlc_app_id: Option<String>,
+}
+
+// Contents of steamapps/appmanifest_<appid>.acf
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppManifest {
+    pub app_id: String,
+    pub universe: Option<u64>,
+    pub launcher_path: Option<String>,
+    pub name: String,
+    pub state_flags: u64,
+    pub install_dir: String,
+    pub last_updated: Option<u64>,
+    pub last_played: Option<u64>,
+    pub size_on_disk: Option<u64>,
+    pub staging_size: Option<u64>,
+    pub build_id: Option<u64>,
+    pub last_owner: Option<String>,
+    pub download_type: Option<u64>,
+    pub update_result: Option<u64>,
+    pub bytes_to_download: Option<u64>,
+    pub bytes_downloaded: Option<u64>,
+    pub bytes_to_stage: Option<u64>,
+    pub bytes_staged: Option<u64>,
+    pub target_build_id: Option<u64>,
+    pub auto_update_behavior: Option<u64>,
+    pub allow_other_downloads_while_running: Option<u64>,
+    pub scheduled_auto_update: Option<u64>,
+    pub full_validate_after_next_update: Option<u64>,
+    pub installed_depots: Vec<InstalledDepot>,
+    pub shared_depots: HashMap<String, String>,
+    pub install_scripts: HashMap<String, String>,
+    pub user_config: HashMap<String, String>,
+    pub mounted_config: HashMap<String, String>,
+}
+
+// EAppState bits from the manifest's StateFlags
+const STATE_UPDATE_REQUIRED: u64 = 0x2;
+const STATE_FULLY_INSTALLED: u64 = 0x4;
+const STATE_FILES_MISSING: u64 = 0x20;
+const STATE_FILES_CORRUPT: u64 = 0x80;
+const STATE_UPDATE_RUNNING: u64 = 0x100;
+const STATE_UPDATE_PAUSED: u64 = 0x200;
+const STATE_UPDATE_STARTED: u64 = 0x400;
+const STATE_UNINSTALLING: u64 = 0x800;
+
+impl AppManifest {
+    // Summarize StateFlags as one of "installed", "update_required", "updating",
+    // "update_paused", "broken", "uninstalling" or "uninstalled"
+    pub fn install_state(&self) -> &'static str {
+        let flags = self.state_flags;
+        if flags & STATE_UNINSTALLING != 0 {
+            "uninstalling"
+        } else if flags & (STATE_UPDATE_RUNNING | STATE_UPDATE_STARTED) != 0 {
+            "updating"
+        } else if flags & STATE_UPDATE_PAUSED != 0 {
+            "update_paused"
+        } else if flags & (STATE_FILES_MISSING | STATE_FILES_CORRUPT) != 0 {
+            "broken"
+        } else if flags & STATE_UPDATE_REQUIRED != 0 {
+            "update_required"
+        } else if flags & STATE_FULLY_INSTALLED != 0 {
+            "installed"
+        } else {
+            "uninstalled"
+        }
+    }
+}
+
+pub fn parse_app_manifest(input: &str) -> Result<AppManifest, String> {
+    let root = parse(input)?;
+    let state = root
+        .get_section("AppState")
+        .ok_or_else(|| "Missing \"AppState\" section".to_string())?;
+
+    let app_id = state
+        .get_str("appid")
+        .ok_or_else(|| "Manifest has no appid".to_string())?
+        .to_string();
+
+    let installed_depots = state
+        .get_section("InstalledDepots")
+        .map(|depots| {
+            depots
+                .sections()
+                .map(|(depot_id, depot)| InstalledDepot {
+                    depot_id: depot_id.to_string(),
+                    manifest: depot.get_str("manifest").unwrap_or_default().to_string(),
+                    size: depot.get_u64("size").unwrap_or(0),
+                    dlc_app_id: depot.get_str("dlcappid").map(str::to_string),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let string_map = |key: &str| {
+        state
+            .get_section(key)
+            .map(KeyValues::string_map)
+            .unwrap_or_default()
+    };
+
+    Ok(AppManifest {
+        app_id,
+        universe: state.get_u64("Universe"),
+        launcher_path: state.get_str("LauncherPath").map(str::to_string),
+        name: state.get_str("name").unwrap_or_default().to_string(),
+        state_flags: state.get_u64("StateFlags").unwrap_or(0),
+        install_dir: state.get_str("installdir").unwrap_or_default().to_string(),
+        last_updated: state.get_u64("LastUpdated"),
+        last_played: state.get_u64("LastPlayed"),
+        size_on_disk: state.get_u64("SizeOnDisk"),
+        staging_size: state.get_u64("StagingSize"),
+        build_id: state.get_u64("buildid"),
+        last_owner: state.get_str("LastOwner").map(str::to_string),
+        download_type: state.get_u64("DownloadType"),
+        update_result: state.get_u64("UpdateResult"),
+        bytes_to_download: state.get_u64("BytesToDownload"),
+        bytes_downloaded: state.get_u64("BytesDownloaded"),
+        bytes_to_stage: state.get_u64("BytesToStage"),
+        bytes_staged: state.get_u64("BytesStaged"),
+        target_build_id: state.get_u64("TargetBuildID"),
+        auto_update_behavior: state.get_u64("AutoUpdateBehavior"),
+        allow_other_downloads_while_running: state.get_u64("AllowOtherDownloadsWhileRunning"),
+        scheduled_auto_update: state.get_u64("ScheduledAutoUpdate"),
+        full_validate_after_next_update: state.get_u64("FullValidateAfterNextUpdate"),
+        installed_depots,
+        shared_depots: string_map("SharedDepots"),
+        install_scripts: string_map("InstallScripts"),
+        user_config: string_map("UserConfig"),
+        mounted_config: string_map("MountedConfig"),
+    })
+}
+
+// Offset between a 64-bit SteamID and the 32-bit account id used for userdata/<id>
+const STEAM_ID64_BASE: u64 = 76561197960265728;
+
+// An account that has logged in to Steam on this machine (config/loginusers.vdf)
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoginUser {
+    pub steam_id64: u64,
+    pub account_id: u32,
+    pub account_name: String,
+    pub persona_name: String,
+    pub most_recent: bool,
+    pub timestamp: Option<u64>,
+}
+
+pub fn parse_login_users(input: &str) -> Result<Vec<LoginUser>, String> {
+    let root = parse(input)?;
+    let users = root
+        .get_section("users")
+        .ok_or_else(|| "Missing \"users\" section".to_string())?;
+
+    Ok(users
+        .sections()
+        .filter_map(|(steam_id, user)| {
+            let steam_id64: u64 = steam_id.parse().ok()?;
+            Some(LoginUser {
+                steam_id64,
+                account_id: steam_id64.checked_sub(STEAM_ID64_BASE)? as u32,
+                account_name: user.get_str("AccountName").unwrap_or_default().to_string(),
+                persona_name: user.get_str("PersonaName").unwrap_or_default().to_string(),
+                most_recent: user.get_u64("MostRecent") == Some(1),
+                timestamp: user.get_u64("Timestamp"),
+            })
+        })
+        .collect())
+}
+
+// Binary KeyValues, as used by userdata/<id>/config/shortcuts.vdf
+const BINARY_SECTION: u8 = 0x00;
+const BINARY_STRING: u8 = 0x01;
+const BINARY_INT32: u8 = 0x02;
+const BINARY_FLOAT32: u8 = 0x03;
+const BINARY_POINTER: u8 = 0x04;
+const BINARY_WIDE_STRING: u8 = 0x05;
+const BINARY_COLOR: u8 = 0x06;
+const BINARY_UINT64: u8 = 0x07;
+const BINARY_END: u8 = 0x08;
+const BINARY_INT64: u8 = 0x0A;
+
+struct BinaryReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BinaryReader<'a> {
+    fn byte(&mut self) -> Result<Option<u8>, String> {
+        match self.data.get(self.pos) {
+            Some(&b) => {
+                self.pos += 1;
+                Ok(Some(b))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self.pos + len;
+        let bytes = self
+            .data
+            .get(self.pos..end)
+            .ok_or_else(|| format!("Unexpected end of data at offset {}", self.pos))?;
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn cstring(&mut self) -> Result<String, String> {
+        let rest = &self.data[self.pos..];
+        let len = rest
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or_else(|| format!("Unterminated string at offset {}", self.pos))?;
+        let text = String::from_utf8_lossy(&rest[..len]).into_owned();
+        self.pos += len + 1;
+        Ok(text)
+    }
+}
+
+// Parse binary KeyValues. Integer and float values are returned as their decimal string
+// form so the result can be read with the same accessors as the text format.
+pub fn parse_binary(data: &[u8]) -> Result<KeyValues, String> {
+    let mut reader = BinaryReader { data, pos: 0 };
+    parse_binary_section(&mut reader, false)
+}
+
+fn parse_binary_section(reader: &mut BinaryReader, nested: bool) -> Result<KeyValues, String> {
+    let mut section = KeyValues::default();
+
+    loop {
+        let kind = match reader.byte()? {
+            Some(BINARY_END) => return Ok(section),
+            Some(kind) => kind,
+            None if nested => return Err("Unexpected end of data inside section".to_string()),
+            None => return Ok(section),
+        };
+        let key = reader.cstring()?;
+
+        let value = match kind {
+            BINARY_SECTION => KeyValue::Section(parse_binary_section(reader, true)?),
+            BINARY_STRING => KeyValue::String(reader.cstring()?),
+            BINARY_INT32 | BINARY_POINTER | BINARY_COLOR => {
+                let bytes = reader.take(4)?;
+                KeyValue::String(i32::from_le_bytes(bytes.try_into().unwrap()).to_string())
+            }
+            BINARY_FLOAT32 => {
+                let bytes = reader.take(4)?;
+                KeyValue::String(f32::from_le_bytes(bytes.try_into().unwrap()).to_string())
+            }
+            BINARY_UINT64 => {
+                let bytes = reader.take(8)?;
+                KeyValue::String(u64::from_le_bytes(bytes.try_into().unwrap()).to_string())
+            }
+            BINARY_INT64 => {
+                let bytes = reader.take(8)?;
+                KeyValue::String(i64::from_le_bytes(bytes.try_into().unwrap()).to_string())
+            }
+            BINARY_WIDE_STRING => {
+                let mut units = Vec::new();
+                loop {
+                    let bytes = reader.take(2)?;
+                    let unit = u16::from_le_bytes([bytes[0], bytes[1]]);
+                    if unit == 0 {
+                        break;
+                    }
+                    units.push(unit);
+                }
+                KeyValue::String(String::from_utf16_lossy(&units))
+            }
+            other => {
+                return Err(format!(
+                    "Unknown value type 0x{:02x} for key \"{}\" at offset {}",
+                    other, key, reader.pos
+                ));
+            }
+        };
+
+        section.entries.push((key, value));
+    }
+}
+
+// A non-Steam game added to the Steam client (entry of shortcuts.vdf)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shortcut {
+    pub app_id: u32,
+    pub app_name: String,
+    pub exe: String,
+    pub start_dir: String,
+    pub icon: String,
+    pub launch_options: String,
+    pub is_hidden: bool,
+    pub last_play_time: Option<u64>,
+    pub tags: Vec<String>,
+}
+
+// Steam's shortcut id: CRC32 of the quoted exe followed by the name, with the high bit set.
+// Only used when the file predates the stored "appid" field.
+pub fn shortcut_app_id(exe: &str, app_name: &str) -> u32 {
+    crc32(format!("{}{}", exe, app_name).as_bytes()) | 0x8000_0000
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+pub fn parse_shortcuts(data: &[u8]) -> Result<Vec<Shortcut>, String> {
+    let root = parse_binary(data)?;
+    let shortcuts = root
+        .get_section("shortcuts")
+        .ok_or_else(|| "Missing \"shortcuts\" section".to_string())?;
+
+    Ok(shortcuts
+        .sections()
+        .map(|(_, entry)| {
+            let app_name = entry.get_str("AppName").unwrap_or_default().to_string();
+            let exe = entry.get_str("Exe").unwrap_or_default().to_string();
+            // Stored as a signed int32; reinterpret the bits as the unsigned id Steam shows
+            let app_id = entry
+                .get_str("appid")
+                .and_then(|id| id.parse::<i64>().ok())
+                .map(|id| id as u32)
+                .filter(|&id| id != 0)
+                .unwrap_or_else(|| shortcut_app_id(&exe, &app_name));
+            let tags = entry
+                .get_section("tags")
+                .map(|tags| {
+                    tags.iter()
+                        .filter_map(|(_, tag)| match tag {
+                            KeyValue::String(tag) => Some(tag.clone()),
+                            KeyValue::Section(_) => None,
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            Shortcut {
+                app_id,
+                app_name,
+                exe,
+                start_dir: entry.get_str("StartDir").unwrap_or_default().to_string(),
+                icon: entry.get_str("icon").unwrap_or_default().to_string(),
+                launch_options: entry
+                    .get_str("LaunchOptions")
+                    .unwrap_or_default()
+                    .to_string(),
+                is_hidden: entry.get_str("IsHidden").is_some_and(|v| v != "0"),
+                last_play_time: entry.get_u64("LastPlayTime").filter(|&t| t > 0),
+                tags,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_library_folders_fixture() {
+        let libraries = parse_library_folders(include_str!("libraryfolders.vdf")).unwrap();
+
+        assert_eq!(libraries.len(), 5);
+        assert_eq!(
+            libraries[0].path,
+            PathBuf::from("C:\\Program Files (x86)\\Steam")
+        );
+        assert_eq!(
+            libraries[0].content_id.as_deref(),
+            Some("3923732609870923804")
+        );
+        assert_eq!(libraries[0].apps, vec![("228980".to_string(), 226466092)]);
+        assert_eq!(libraries[3].path, PathBuf::from("G:\\SteamLibrary"));
+        assert_eq!(libraries[3].total_size, Some(1000203087872));
+        assert_eq!(libraries[3].app_ids(), vec!["3132990", "3489700"]);
+        assert_eq!(libraries[4].app_ids(), vec!["2452280"]);
+    }
+
+    #[test]
+    fn test_parse_library_folders_linux_fixture() {
+        let libraries =
+            parse_library_folders(include_str!("fixtures/libraryfolders_linux.vdf")).unwrap();
+
+        assert_eq!(libraries.len(), 2);
+        assert_eq!(
+            libraries[0].path,
+            PathBuf::from("/home/deck/.local/share/Steam")
+        );
+        assert_eq!(libraries[0].label, "");
+        assert_eq!(libraries[0].app_ids(), vec!["228980", "1245620", "1493710"]);
+        // Label with escaped quotes and a "contentstatsid" key that is not a library
+        assert_eq!(libraries[1].path, PathBuf::from("/run/media/deck/SD Card"));
+        assert_eq!(libraries[1].label, "My \"fast\" card");
+        assert!(libraries[1].apps.is_empty());
+    }
+
+    #[test]
+    fn test_parse_legacy_library_folders() {
+        let input = "\"LibraryFolders\"\n{\n\t\"TimeNextStatsReport\"\t\t\"1561832478\"\n\t\"1\"\t\t\"D:\\\\Games\\\\Steam\"\n}\n";
+        let libraries = parse_library_folders(input).unwrap();
+
+        assert_eq!(libraries.len(), 1);
+        assert_eq!(libraries[0].path, PathBuf::from("D:\\Games\\Steam"));
+    }
+
+    #[test]
+    fn test_parse_app_manifest_fixture() {
+        let manifest =
+            parse_app_manifest(include_str!("fixtures/appmanifest_1245620.acf")).unwrap();
+
+        assert_eq!(manifest.app_id, "1245620");
+        assert_eq!(manifest.name, "ELDEN RING");
+        assert_eq!(manifest.install_dir, "ELDEN RING");
+        assert_eq!(manifest.state_flags, 4);
+        assert_eq!(manifest.last_updated, Some(1718303204));
+        assert_eq!(manifest.last_played, Some(1719432010));
+        assert_eq!(manifest.size_on_disk, Some(50356342213));
+        assert_eq!(manifest.build_id, Some(14751398));
+        assert_eq!(manifest.last_owner.as_deref(), Some("76561198012345678"));
+        assert_eq!(manifest.auto_update_behavior, Some(0));
+        assert_eq!(manifest.installed_depots.len(), 2);
+        assert_eq!(manifest.installed_depots[0].depot_id, "1245621");
+        assert_eq!(manifest.installed_depots[0].size, 50355851021);
+        assert_eq!(
+            manifest.installed_depots[1].dlc_app_id.as_deref(),
+            Some("2778580")
+        );
+        assert_eq!(
+            manifest.shared_depots.get("228988").map(String::as_str),
+            Some("228980")
+        );
+        assert_eq!(
+            manifest.user_config.get("language").map(String::as_str),
+            Some("english")
+        );
+        assert_eq!(
+            manifest.mounted_config.get("language").map(String::as_str),
+            Some("english")
+        );
+        assert_eq!(manifest.install_state(), "installed");
+    }
+
+    #[test]
+    fn test_install_state() {
+        let state = |state_flags| {
+            AppManifest {
+                state_flags,
+                ..parse_app_manifest(include_str!("fixtures/appmanifest_1245620.acf")).unwrap()
+            }
+            .install_state()
+        };
+
+        assert_eq!(state(4), "installed");
+        assert_eq!(state(6), "update_required");
+        assert_eq!(state(1030), "updating");
+        assert_eq!(state(518), "update_paused");
+        assert_eq!(state(36), "broken");
+        assert_eq!(state(2052), "uninstalling");
+        assert_eq!(state(1), "uninstalled");
+        assert_eq!(state(0), "uninstalled");
+    }
+
+    #[test]
+    fn test_parse_login_users() {
+        let content = r#""users"
+{
+	"76561197960287930"
+	{
+		"AccountName"		"gaben"
+		"PersonaName"		"Rabscuttle"
+		"RememberPassword"		"1"
+		"MostRecent"		"1"
+		"Timestamp"		"1719432010"
+	}
+	"76561198000000001"
+	{
+		"AccountName"		"second"
+		"PersonaName"		"Second Account"
+		"MostRecent"		"0"
+	}
+	"not-a-steam-id"
+	{
+		"AccountName"		"broken"
+	}
+}"#;
+        let users = parse_login_users(content).unwrap();
+
+        assert_eq!(users.len(), 2);
+        assert_eq!(users[0].account_id, 22202);
+        assert_eq!(users[0].persona_name, "Rabscuttle");
+        assert!(users[0].most_recent);
+        assert_eq!(users[0].timestamp, Some(1719432010));
+        assert_eq!(users[1].account_name, "second");
+        assert!(!users[1].most_recent);
+        assert!(parse_login_users("\"other\" {}").is_err());
+    }
+
+    #[test]
+    fn test_parse_inline_braces_and_escapes() {
+        let input = r#""AppState" { "appid" "10" "name" "Say \"Hi\" \\ Bye" "UserConfig" { "language" "english" } }
+// trailing comment
+"#;
+        let root = parse(input).unwrap();
+        let state = root.get_section("appstate").unwrap();
+
+        assert_eq!(state.get_str("name"), Some("Say \"Hi\" \\ Bye"));
+        assert_eq!(
+            state.get_section("UserConfig").unwrap().get_str("language"),
+            Some("english")
+        );
+    }
+
+    #[test]
+    fn test_parse_unquoted_and_conditionals() {
+        let input = "Root\n{\n\tkey value [$WIN32]\n\tother \"x\" // comment\n}\n";
+        let root = parse(input).unwrap();
+        let section = root.get_section("Root").unwrap();
+
+        assert_eq!(section.get_str("key"), Some("value"));
+        assert_eq!(section.get_str("other"), Some("x"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("\"a\" { \"b\" \"c\"").is_err());
+        assert!(parse("\"a\" \"unterminated").is_err());
+        assert!(parse("}").is_err());
+        assert!(parse("\"a\"").is_err());
+        assert!(parse_app_manifest("\"Other\" { }").is_err());
+    }
+
+    // Encode a shortcuts.vdf the way Steam writes it
+    fn shortcut_entry(
+        out: &mut Vec<u8>,
+        index: &str,
+        fields: &[(&str, Option<&str>, Option<i32>)],
+    ) {
+        out.push(BINARY_SECTION);
+        out.extend_from_slice(index.as_bytes());
+        out.push(0);
+        for (key, text, int) in fields {
+            if let Some(text) = text {
+                out.push(BINARY_STRING);
+                out.extend_from_slice(key.as_bytes());
+                out.push(0);
+                out.extend_from_slice(text.as_bytes());
+                out.push(0);
+            } else if let Some(int) = int {
+                out.push(BINARY_INT32);
+                out.extend_from_slice(key.as_bytes());
+                out.push(0);
+                out.extend_from_slice(&int.to_le_bytes());
+            }
+        }
+        out.push(BINARY_SECTION);
+        out.extend_from_slice(b"tags\0");
+        out.push(BINARY_STRING);
+        out.extend_from_slice(b"0\0Emulation\0");
+        out.push(BINARY_END);
+        out.push(BINARY_END);
+    }
+
+    #[test]
+    fn test_parse_shortcuts() {
+        let mut data = vec![BINARY_SECTION];
+        data.extend_from_slice(b"shortcuts\0");
+        shortcut_entry(
+            &mut data,
+            "0",
+            &[
+                ("appid", None, Some(-1_558_359_014)),
+                ("AppName", Some("Dolphin"), None),
+                ("Exe", Some("\"/usr/bin/dolphin-emu\""), None),
+                ("StartDir", Some("\"/usr/bin/\""), None),
+                ("LastPlayTime", None, Some(1_719_432_010)),
+            ],
+        );
+        shortcut_entry(
+            &mut data,
+            "1",
+            &[
+                ("AppName", Some("Old Shortcut"), None),
+                ("Exe", Some("\"/opt/game/run.sh\""), None),
+                ("IsHidden", None, Some(1)),
+            ],
+        );
+        data.push(BINARY_END);
+        data.push(BINARY_END);
+
+        let shortcuts = parse_shortcuts(&data).unwrap();
+
+        assert_eq!(shortcuts.len(), 2);
+        assert_eq!(shortcuts[0].app_name, "Dolphin");
+        assert_eq!(shortcuts[0].app_id, 2_736_608_282);
+        assert_eq!(shortcuts[0].start_dir, "\"/usr/bin/\"");
+        assert_eq!(shortcuts[0].last_play_time, Some(1_719_432_010));
+        assert_eq!(shortcuts[0].tags, vec!["Emulation"]);
+        assert!(!shortcuts[0].is_hidden);
+        // No stored appid: falls back to the CRC-derived id
+        assert_eq!(
+            shortcuts[1].app_id,
+            shortcut_app_id("\"/opt/game/run.sh\"", "Old Shortcut")
+        );
+        assert!(shortcuts[1].app_id & 0x8000_0000 != 0);
+        assert!(shortcuts[1].is_hidden);
+    }
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_parse_binary_errors() {
+        assert!(parse_binary(&[BINARY_SECTION, b'a', 0]).is_err());
+        assert!(parse_binary(&[BINARY_INT32, b'a', 0, 1, 2]).is_err());
+        assert!(parse_binary(&[0x42, b'a', 0]).is_err());
+        assert!(parse_shortcuts(&[BINARY_END]).is_err());
+    }
+}
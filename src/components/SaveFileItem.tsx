@@ -268,8 +268,8 @@ const SaveFileItem: React.FC<SaveFileItemProps> = ({
                           let fileName = saveFile.file_name;
                           if (!fileName.toLowerCase().endsWith(".zip")) {
                             // Check if it was originally a directory by looking at the backup name pattern
-                            // Directory backups are named like "backup_20250728_141201" without extension
-                            if (fileName.match(/^backup_\d{8}_\d{6}$/)) {
+                            // Directory backups are named like "backup_20250728_141201_123" without extension
+                            if (fileName.match(/^backup_\d{8}_\d{6}(_\d{3})?$/)) {
                               fileName = `${fileName}.zip`;
                             }
                           }